@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Known answers to both parts of each day, `None` for a part not solved yet.
+pub type Answers = BTreeMap<u32, [Option<String>; 2]>;
+
+/// Reads answers listed one day per line, as the day followed by the answers to both parts.
+/// Lines starting with `#` are comments, and `?` stands for an unknown answer.
+pub fn parse_answers(content: &str) -> Result<Answers, String> {
+    let mut answers = Answers::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        let day = match fields[..] {
+            [day, _, _] => day
+                .parse::<u32>()
+                .map_err(|_| format!("Line {}: invalid day '{}'", index + 1, day))?,
+            _ => {
+                return Err(format!(
+                    "Line {}: expected a day and two answers",
+                    index + 1
+                ))
+            }
+        };
+        let answer = |field: &str| (field != "?").then(|| field.to_string());
+        if answers
+            .insert(day, [answer(fields[1]), answer(fields[2])])
+            .is_some()
+        {
+            return Err(format!("Line {}: day {} listed twice", index + 1, day));
+        }
+    }
+    Ok(answers)
+}
+
+/// File listing the answers of the year, in the workspace at `root`.
+pub fn answers_path(root: &Path, year: u32) -> PathBuf {
+    root.join("answers").join(format!("{}.txt", year))
+}
+
+/// Line listing a day without any known answer.
+pub fn empty_entry(day: u32) -> String {
+    format!("{} ? ?\n", day)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+    use crate::puzzle::Puzzle;
+    use crate::solutions::answers;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# comment\n1 24000 45000\n\n5 ? ?\n").unwrap();
+        assert_eq!(
+            answers[&1],
+            [Some("24000".to_string()), Some("45000".to_string())]
+        );
+        assert_eq!(answers[&5], [None, None]);
+        assert_eq!(parse_answers(&empty_entry(6)).unwrap()[&6], [None, None]);
+        assert_eq!(
+            parse_answers("1 2\n"),
+            Err("Line 1: expected a day and two answers".to_string())
+        );
+        assert_eq!(
+            parse_answers("1 2 3\n1 2 3\n"),
+            Err("Line 2: day 1 listed twice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_committed_inputs_give_known_answers() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for entry in fs::read_dir(root.join("answers")).unwrap() {
+            let path = entry.unwrap().path();
+            let year = path
+                .file_stem()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .parse::<u32>()
+                .unwrap();
+            assert_eq!(path, answers_path(&root, year));
+
+            for (day, expected) in parse_answers(&fs::read_to_string(&path).unwrap()).unwrap() {
+                let puzzle = Puzzle::new(year, day);
+                let directory = root.join(puzzle.crate_name()).join("src");
+                let input = match fs::read_to_string(directory.join("input.txt"))
+                    .or_else(|_| fs::read_to_string(directory.join("input")))
+                {
+                    Ok(input) => input,
+                    Err(_) => continue,
+                };
+
+                let solved = answers(puzzle, &input).unwrap();
+                for (answer, expected) in solved.iter().zip(expected) {
+                    if let Some(expected) = expected {
+                        assert_eq!(*answer, expected, "{}", puzzle);
+                    }
+                }
+            }
+        }
+    }
+}
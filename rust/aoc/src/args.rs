@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+/// Options of a command, given as `--name value`.
+pub struct Args {
+    values: Vec<String>,
+}
+
+impl Args {
+    pub fn new(values: &[String]) -> Args {
+        Args {
+            values: values.to_vec(),
+        }
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        let index = self.values.iter().position(|value| value == name)?;
+        self.values.get(index + 1).map(|value| value.as_str())
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.values.iter().any(|value| value == name)
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.value(name) {
+            Some(value) => value
+                .parse::<T>()
+                .map(Some)
+                .map_err(|_| format!("Invalid value '{}' for {}", value, name)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn required<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.parse(name)?
+            .ok_or_else(|| format!("Missing required option {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn args(values: &[&str]) -> Args {
+        Args::new(
+            &values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>(),
+        )
+    }
+
+    #[test]
+    fn test_values_and_flags() {
+        let args = args(&["--day", "4", "--size", "x", "--all"]);
+        assert!(args.flag("--all"));
+        assert!(!args.flag("--list"));
+        assert_eq!(args.required::<u32>("--day"), Ok(4));
+        assert_eq!(args.parse::<u64>("--seed"), Ok(None));
+        assert_eq!(
+            args.required::<usize>("--size"),
+            Err("Invalid value 'x' for --size".to_string())
+        );
+        assert_eq!(
+            args.required::<u64>("--seed"),
+            Err("Missing required option --seed".to_string())
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use aoc_common::Rng;
+
+use crate::args::Args;
+use crate::puzzle::Puzzle;
+use crate::solutions::{answers, generate, oracle_answers};
+
+pub const USAGE: &str = "aoc differential [--year N] --day N [--runs N] [--size N] [--seed N]";
+
+/// Generated input on which the solution and the naive oracle disagree.
+#[derive(PartialEq, Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub solution: [String; 2],
+    pub oracle: [String; 2],
+}
+
+/// Compares the solution of a puzzle with its oracle on many generated inputs.
+pub fn run(args: &Args) -> Result<(), String> {
+    let puzzle = Puzzle::from_args(args)?;
+    let runs = args.parse::<u64>("--runs")?.unwrap_or(1000);
+    let size = args.parse::<usize>("--size")?.unwrap_or(100);
+    let seed = args.parse::<u64>("--seed")?.unwrap_or(0);
+
+    match find_mismatch(puzzle, runs, size, seed)? {
+        None => {
+            println!("Solution and oracle agree on {} inputs of {}", runs, puzzle);
+            Ok(())
+        }
+        Some(mismatch) => Err(report(puzzle, &mismatch)),
+    }
+}
+
+/// Describes a mismatch with the input it was found on and how to generate it again.
+pub fn report(puzzle: Puzzle, mismatch: &Mismatch) -> String {
+    format!(
+        "Solution and oracle disagree on the input of size {} and seed {}\n\
+         Solution: {:?}\nOracle: {:?}\nReproduce with: aoc generate --year {} --day {} --size {} --seed {}\n\
+         Input:\n{}",
+        mismatch.size,
+        mismatch.seed,
+        mismatch.solution,
+        mismatch.oracle,
+        puzzle.year,
+        puzzle.day,
+        mismatch.size,
+        mismatch.seed,
+        mismatch.input.trim_end()
+    )
+}
+
+/// First mismatch over `runs` inputs, growing from one item up to `max_size` items.
+///
+/// Every input uses its own seed, so a mismatch can be generated again on its own.
+pub fn find_mismatch(
+    puzzle: Puzzle,
+    runs: u64,
+    max_size: usize,
+    seed: u64,
+) -> Result<Option<Mismatch>, String> {
+    first_mismatch(
+        runs,
+        max_size,
+        seed,
+        |size, rng| generate(puzzle, size, rng),
+        |input| answers(puzzle, input),
+        |input| oracle_answers(puzzle, input),
+    )
+}
+
+fn first_mismatch(
+    runs: u64,
+    max_size: usize,
+    seed: u64,
+    generate: impl Fn(usize, &mut Rng) -> Result<String, String>,
+    solution: impl Fn(&str) -> Result<[String; 2], String>,
+    oracle: impl Fn(&str) -> Result<[String; 2], String>,
+) -> Result<Option<Mismatch>, String> {
+    for run in 0..runs {
+        let seed = seed.wrapping_add(run);
+        let size = 1 + run as usize % max_size.max(1);
+        let input = generate(size, &mut Rng::new(seed))?;
+
+        let solution = solution(&input)?;
+        let oracle = oracle(&input)?;
+        if solution != oracle {
+            return Ok(Some(Mismatch {
+                seed,
+                size,
+                input,
+                solution,
+                oracle,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_solutions_agree_with_oracles() {
+        for day in 1..=4 {
+            assert_eq!(
+                find_mismatch(Puzzle::new(2022, day), 250, 40, 2022),
+                Ok(None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reports_first_mismatch() {
+        let puzzle = Puzzle::new(2022, 4);
+        // Counts one overlap too many once there are three assignments or more
+        let wrong = |input: &str| {
+            let [one, two] = answers(puzzle, input)?;
+            Ok(match input.lines().count() {
+                0..=2 => [one, two],
+                _ => [one, (two.parse::<u64>().unwrap() + 1).to_string()],
+            })
+        };
+        let mismatch = first_mismatch(
+            10,
+            5,
+            7,
+            |size, rng| generate(puzzle, size, rng),
+            wrong,
+            |input| oracle_answers(puzzle, input),
+        )
+        .unwrap()
+        .unwrap();
+
+        let input = generate(puzzle, 3, &mut Rng::new(9)).unwrap();
+        let [one, two] = oracle_answers(puzzle, &input).unwrap();
+        let solution = [one.clone(), (two.parse::<u64>().unwrap() + 1).to_string()];
+        assert_eq!(
+            mismatch,
+            Mismatch {
+                seed: 9,
+                size: 3,
+                input: input.clone(),
+                solution: solution.clone(),
+                oracle: [one.clone(), two.clone()],
+            }
+        );
+        assert_eq!(
+            report(puzzle, &mismatch),
+            format!(
+                "Solution and oracle disagree on the input of size 3 and seed 9\n\
+                 Solution: {:?}\nOracle: {:?}\n\
+                 Reproduce with: aoc generate --year 2022 --day 4 --size 3 --seed 9\n\
+                 Input:\n{}",
+                solution,
+                [one, two],
+                input.trim_end()
+            )
+        );
+    }
+}
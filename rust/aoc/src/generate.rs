@@ -0,0 +1,71 @@
+use std::fs;
+
+use aoc_common::Rng;
+
+use crate::args::Args;
+use crate::puzzle::Puzzle;
+use crate::solutions::{generate, oracle_answers};
+
+pub const USAGE: &str =
+    "aoc generate [--year N] --day N --size N [--seed N] [--output PATH] [--answers PATH]";
+
+/// Writes a random input of the given size, and optionally the answers expected for it.
+pub fn run(args: &Args) -> Result<(), String> {
+    let puzzle = Puzzle::from_args(args)?;
+    let size = args.required::<usize>("--size")?;
+    let seed = args.parse::<u64>("--seed")?.unwrap_or(0);
+
+    let input = generate(puzzle, size, &mut Rng::new(seed))?;
+    match args.value("--output") {
+        Some(path) => fs::write(path, &input).map_err(|error| format!("{}: {}", path, error))?,
+        None => print!("{}", input),
+    }
+
+    if let Some(path) = args.value("--answers") {
+        let answers = expected_answers(puzzle, &input)?;
+        fs::write(path, answers).map_err(|error| format!("{}: {}", path, error))?;
+    }
+    Ok(())
+}
+
+// Computed by the naive oracle, so the file can catch a wrong solution
+fn expected_answers(puzzle: Puzzle, input: &str) -> Result<String, String> {
+    let [part_one, part_two] = oracle_answers(puzzle, input)?;
+    Ok(format!("Part one: {}\nPart two: {}\n", part_one, part_two))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for day in 1..=4 {
+            let puzzle = Puzzle::new(2022, day);
+            let input = generate(puzzle, 300, &mut Rng::new(day as u64)).unwrap();
+            assert!(crate::solutions::answers(puzzle, &input).is_ok());
+            assert_eq!(
+                input,
+                generate(puzzle, 300, &mut Rng::new(day as u64)).unwrap()
+            );
+        }
+        assert!(generate(Puzzle::new(2022, 5), 10, &mut Rng::new(0)).is_err());
+    }
+
+    #[test]
+    fn test_expected_answers_come_from_the_oracle() {
+        let puzzle = Puzzle::new(2022, 2);
+        let input = generate(puzzle, 100, &mut Rng::new(7)).unwrap();
+        let [part_one, part_two] = day_2::oracle::answers(&input).unwrap();
+        assert_eq!(
+            expected_answers(puzzle, &input),
+            Ok(format!("Part one: {}\nPart two: {}\n", part_one, part_two))
+        );
+        assert_eq!(
+            expected_answers(Puzzle::new(2022, 5), ""),
+            Err("No oracle for 2022 day 5".to_string())
+        );
+        assert!(generate(Puzzle::new(2023, 1), 10, &mut Rng::new(0)).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::puzzle::Puzzle;
+
+/// Source of puzzle inputs that are not cached yet.
+pub trait Fetcher {
+    fn fetch(&self, puzzle: Puzzle) -> Result<String, String>;
+}
+
+/// Downloads inputs from the puzzle site, authenticated by the session cookie of the user.
+pub struct HttpFetcher {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: &str, session: Option<String>) -> HttpFetcher {
+        HttpFetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    /// Uses `AOC_SESSION` for the cookie and `AOC_URL` to point at another server.
+    pub fn from_env() -> HttpFetcher {
+        let base_url =
+            env::var("AOC_URL").unwrap_or_else(|_| "https://adventofcode.com".to_string());
+        HttpFetcher::new(&base_url, env::var("AOC_SESSION").ok())
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, puzzle: Puzzle) -> Result<String, String> {
+        let session = self.session.as_ref().ok_or_else(|| {
+            format!(
+                "No input cached for {}, set AOC_SESSION to download it",
+                puzzle
+            )
+        })?;
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, puzzle.year, puzzle.day);
+        ureq::get(&url)
+            .header("Cookie", format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|error| format!("{}: {}", url, error))
+    }
+}
+
+pub const USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+
+/// Looks up inputs in a cache directory, as `<year>/<day>.txt`, and fetches those missing.
+///
+/// A cached input is never downloaded again, so the fetcher is asked at most once per day.
+pub struct InputProvider<F: Fetcher> {
+    cache_dir: PathBuf,
+    fetcher: F,
+}
+
+impl<F: Fetcher> InputProvider<F> {
+    pub fn new(cache_dir: &Path, fetcher: F) -> InputProvider<F> {
+        InputProvider {
+            cache_dir: cache_dir.to_path_buf(),
+            fetcher,
+        }
+    }
+
+    pub fn path(&self, puzzle: Puzzle) -> PathBuf {
+        self.cache_dir
+            .join(puzzle.year.to_string())
+            .join(format!("{}.txt", puzzle.day))
+    }
+
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, String> {
+        let path = self.path(puzzle);
+        if path.exists() {
+            return fs::read_to_string(&path).map_err(|error| describe(&path, error));
+        }
+
+        let input = self.fetcher.fetch(puzzle)?;
+        // Written next to its final place first, so an interrupted write is never cached
+        let partial = path.with_extension("part");
+        let directory = path.parent().unwrap();
+        fs::create_dir_all(directory).map_err(|error| describe(directory, error))?;
+        fs::write(&partial, &input).map_err(|error| describe(&partial, error))?;
+        fs::rename(&partial, &path).map_err(|error| describe(&path, error))?;
+        Ok(input)
+    }
+}
+
+/// Input committed in the crate of a day as `src/input.txt`, or `src/input` for day 1 of 2022,
+/// from before inputs were cached outside the repository.
+pub fn committed_input(crate_dir: &Path) -> Option<PathBuf> {
+    ["input.txt", "input"]
+        .iter()
+        .map(|file| crate_dir.join("src").join(file))
+        .find(|path| path.exists())
+}
+
+/// The input committed in the crate of the puzzle in the workspace at `root`, otherwise the
+/// one the provider finds, so inputs still in the repository can be solved offline.
+pub fn committed_or_provided<F: Fetcher>(
+    root: &Path,
+    provider: &InputProvider<F>,
+    puzzle: Puzzle,
+) -> Result<String, String> {
+    match committed_input(&root.join(puzzle.crate_name())) {
+        Some(path) => fs::read_to_string(&path).map_err(|error| describe(&path, error)),
+        None => provider.input(puzzle),
+    }
+}
+
+/// `AOC_CACHE_DIR` if set, otherwise `aoc` in the cache directory of the user.
+pub fn default_cache_dir() -> Result<PathBuf, String> {
+    if let Some(directory) = env::var_os("AOC_CACHE_DIR") {
+        return Ok(PathBuf::from(directory));
+    }
+    if let Some(directory) = env::var_os("XDG_CACHE_HOME") {
+        return Ok(PathBuf::from(directory).join("aoc"));
+    }
+    match env::var_os("HOME") {
+        Some(home) => Ok(PathBuf::from(home).join(".cache").join("aoc")),
+        None => Err("Cannot find a cache directory, set AOC_CACHE_DIR".to_string()),
+    }
+}
+
+fn describe(path: &Path, error: std::io::Error) -> String {
+    format!("{}: {}", path.display(), error)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::stub::StubServer;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn test_fetches_once_with_session_cookie() {
+        let server = StubServer::start(|request| (200, format!("input of {}\n", request.path)));
+        let directory = cache_dir("fetch");
+        let provider = InputProvider::new(
+            &directory,
+            HttpFetcher::new(&server.url(), Some("secret".to_string())),
+        );
+
+        assert_eq!(
+            provider.input(Puzzle::new(2022, 4)),
+            Ok("input of /2022/day/4/input\n".to_string())
+        );
+        assert_eq!(
+            provider.input(Puzzle::new(2022, 4)),
+            Ok("input of /2022/day/4/input\n".to_string())
+        );
+        assert!(directory.join("2022").join("4.txt").exists());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(
+            requests[0].header("cookie"),
+            Some("session=secret".to_string())
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_failed_download_is_not_cached() {
+        let server = StubServer::start(|_| (404, "Not found".to_string()));
+        let directory = cache_dir("missing");
+        let provider = InputProvider::new(
+            &directory,
+            HttpFetcher::new(&server.url(), Some("secret".to_string())),
+        );
+
+        assert!(provider.input(Puzzle::new(2022, 26)).is_err());
+        assert!(!provider.path(Puzzle::new(2022, 26)).exists());
+        assert!(provider.input(Puzzle::new(2022, 26)).is_err());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_committed_input_is_read_offline() {
+        let root = cache_dir("committed");
+        fs::create_dir_all(root.join("day_1/src")).unwrap();
+        fs::write(root.join("day_1/src/input"), "1000\n").unwrap();
+        fs::create_dir_all(root.join("day_4/src")).unwrap();
+        fs::write(root.join("day_4/src/input.txt"), "2-4,6-8\n").unwrap();
+        let cache = root.join("cache");
+        let provider = InputProvider::new(&cache, HttpFetcher::new("http://127.0.0.1:9", None));
+
+        let input = |day| committed_or_provided(&root, &provider, Puzzle::new(2022, day));
+        assert_eq!(input(1), Ok("1000\n".to_string()));
+        assert_eq!(input(4), Ok("2-4,6-8\n".to_string()));
+        assert_eq!(
+            input(2),
+            Err("No input cached for 2022 day 2, set AOC_SESSION to download it".to_string())
+        );
+        assert!(!cache.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session_is_reported() {
+        let fetcher = HttpFetcher::new("http://127.0.0.1:9", None);
+        assert_eq!(
+            fetcher.fetch(Puzzle::new(2022, 1)),
+            Err("No input cached for 2022 day 1, set AOC_SESSION to download it".to_string())
+        );
+    }
+}
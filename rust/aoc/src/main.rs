@@ -0,0 +1,47 @@
+use std::env;
+use std::process;
+
+use args::Args;
+
+mod answers;
+mod args;
+mod differential;
+mod generate;
+mod input;
+mod new;
+mod pool;
+mod puzzle;
+mod report;
+mod run;
+mod solutions;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod watch;
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let result = match args.first().map(|arg| arg.as_str()) {
+        Some("run") => run::run(&Args::new(&args[1..])),
+        Some("submit") => submit::run(&Args::new(&args[1..])),
+        Some("watch") => watch::run(&Args::new(&args[1..])),
+        Some("new") => new::run(&Args::new(&args[1..])),
+        Some("generate") => generate::run(&Args::new(&args[1..])),
+        Some("differential") => differential::run(&Args::new(&args[1..])),
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+        None => Err(format!(
+            "Usage:\n  {}\n  {}\n  {}\n  {}\n  {}\n  {}",
+            run::USAGE,
+            submit::USAGE,
+            watch::USAGE,
+            new::USAGE,
+            generate::USAGE,
+            differential::USAGE
+        )),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,281 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{answers_path, empty_entry, parse_answers};
+use crate::args::Args;
+use crate::puzzle::{Puzzle, DEFAULT_YEAR};
+
+pub const USAGE: &str = "aoc new [--year N] --day N";
+
+// Start of the answers file of a year without any answers yet
+const ANSWERS_HEADER: &str =
+    "# Answers to the puzzle inputs: day, part one and part two, with ? for a part not solved yet\n";
+
+// Files of a new day, relative to its crate
+const TEMPLATE: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../template/Cargo.toml.tmpl")),
+    (".gitignore", include_str!("../template/gitignore.tmpl")),
+    ("src/lib.rs", include_str!("../template/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../template/main.rs.tmpl")),
+    ("src/test-input.txt", ""),
+];
+
+/// Creates the crate of a new puzzle and registers it with the runner.
+pub fn run(args: &Args) -> Result<(), String> {
+    let puzzle = Puzzle::from_args(args)?;
+    scaffold(&workspace_root(), puzzle)?;
+    println!(
+        "Created {} with a stub parser, paste the example into {}/src/test-input.txt",
+        puzzle.crate_name(),
+        puzzle.crate_name()
+    );
+    Ok(())
+}
+
+/// Directory of the workspace holding the runner and every solution.
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// Writes the new crate into the workspace at `root` and adds it to the workspace members, the
+/// dependencies and solutions of the runner, the answers and the README next to `root`.
+pub fn scaffold(root: &Path, puzzle: Puzzle) -> Result<(), String> {
+    let name = puzzle.crate_name();
+    let directory = root.join(&name);
+    if directory.exists() {
+        return Err(format!("{} already exists", directory.display()));
+    }
+    let answers_path = answers_path(root, puzzle.year);
+    let answers = match answers_path.exists() {
+        true => fs::read_to_string(&answers_path)
+            .map_err(|error| format!("{}: {}", answers_path.display(), error))?,
+        false => ANSWERS_HEADER.to_string(),
+    };
+    if parse_answers(&answers)?.contains_key(&puzzle.day) {
+        return Err(format!(
+            "Day {} is already listed in {}",
+            puzzle.day,
+            answers_path.display()
+        ));
+    }
+
+    // Every edit is worked out before anything is written, so a file laid out differently
+    // than expected leaves the workspace as it was
+    let mut files = TEMPLATE
+        .iter()
+        .map(|(file, template)| {
+            let content = template
+                .replace("{{crate}}", &name)
+                .replace("{{day}}", &puzzle.day.to_string());
+            (directory.join(file), content)
+        })
+        .collect::<Vec<(PathBuf, String)>>();
+    files.push(edited(&root.join("Cargo.toml"), |manifest| {
+        let end = manifest
+            .find("members = [")
+            .and_then(|start| manifest[start..].find(']').map(|end| start + end))?;
+        Some(format!(
+            "{}, \"{}\"{}",
+            &manifest[..end],
+            name,
+            &manifest[end..]
+        ))
+    })?);
+    files.push(edited(&root.join("aoc/Cargo.toml"), |manifest| {
+        // After the last solution, which are listed after the other path dependencies
+        let last = manifest.rfind("{ path = \"../")?;
+        let end = last + manifest[last..].find('\n')? + 1;
+        let dependency = format!("{} = {{ path = \"../{}\" }}\n", name, name);
+        Some(format!(
+            "{}{}{}",
+            &manifest[..end],
+            dependency,
+            &manifest[end..]
+        ))
+    })?);
+    files.push(edited(&root.join("aoc/src/solutions.rs"), |solutions| {
+        let table = solutions.find("const SOLUTIONS")?;
+        let end = table + solutions[table..].find("\n];")? + 1;
+        let entry = format!(
+            "    (Puzzle::new({}, {}), registered::<{}::Day{}>()),\n",
+            puzzle.year, puzzle.day, name, puzzle.day
+        );
+        Some(format!(
+            "{}{}{}",
+            &solutions[..end],
+            entry,
+            &solutions[end..]
+        ))
+    })?);
+    files.push((
+        answers_path,
+        format!("{}{}", answers, empty_entry(puzzle.day)),
+    ));
+    files.push(edited(&root.join("../README.md"), |readme| {
+        let title = match puzzle.year {
+            DEFAULT_YEAR => format!("Day {}", puzzle.day),
+            year => format!("{} Day {}", year, puzzle.day),
+        };
+        let section = format!(
+            "## {}\n\n**Input:**  \n**Part One:**  \n**Part Two:**  \n\n",
+            title
+        );
+        // After the section of the last day, whatever follows it
+        let headings = readme
+            .match_indices("## ")
+            .map(|(start, _)| start)
+            .filter(|&start| start == 0 || readme[..start].ends_with('\n'))
+            .collect::<Vec<usize>>();
+        let last_day = headings
+            .iter()
+            .rposition(|&start| is_day_heading(&readme[start..]));
+        Some(match last_day.and_then(|last| headings.get(last + 1)) {
+            Some(&next) => format!("{}{}{}", &readme[..next], section, &readme[next..]),
+            None => format!("{}\n\n{}", readme.trim_end(), section.trim_end()),
+        })
+    })?);
+
+    for (path, content) in files {
+        write(&path, &content)?;
+    }
+    Ok(())
+}
+
+// Whether the line is the heading of a day, `## Day N` or `## YEAR Day N`
+fn is_day_heading(line: &str) -> bool {
+    line.strip_prefix("## ").is_some_and(|title| {
+        title
+            .trim_start_matches(|character: char| character.is_ascii_digit())
+            .trim_start()
+            .starts_with("Day ")
+    })
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    let describe = |error: std::io::Error| format!("{}: {}", path.display(), error);
+    fs::create_dir_all(path.parent().unwrap()).map_err(describe)?;
+    fs::write(path, content).map_err(describe)
+}
+
+// The file with the change applied, which fails if the file is not laid out as expected
+fn edited(
+    path: &Path,
+    change: impl Fn(&str) -> Option<String>,
+) -> Result<(PathBuf, String), String> {
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let changed = change(&content)
+        .ok_or_else(|| format!("{}: cannot find where to register the day", path.display()))?;
+    Ok((path.to_path_buf(), changed))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+
+    use super::*;
+
+    // Copies the files edited by the scaffolding into a new directory
+    fn workspace(name: &str) -> PathBuf {
+        let base = env::temp_dir().join(format!("aoc-new-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let root = base.join("rust");
+        for file in [
+            "Cargo.toml",
+            "aoc/Cargo.toml",
+            "aoc/src/solutions.rs",
+            "answers/2022.txt",
+            "../README.md",
+        ] {
+            let content = fs::read_to_string(source.join(file)).unwrap();
+            write(&root.join(file), &content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_scaffold_registers_day() {
+        let root = workspace("register");
+        scaffold(&root, Puzzle::new(2022, 25)).unwrap();
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("Cargo.toml").contains(", \"day_25\"]"));
+        assert!(read("aoc/Cargo.toml").contains("day_25 = { path = \"../day_25\" }\n"));
+        assert!(read("aoc/src/solutions.rs")
+            .contains("    (Puzzle::new(2022, 25), registered::<day_25::Day25>()),\n];"));
+        assert_eq!(
+            parse_answers(&read("answers/2022.txt")).unwrap()[&25],
+            [None, None]
+        );
+        let readme = read("../README.md");
+        let position = |heading: &str| readme.find(heading).unwrap();
+        assert!(position("## Day 4\n") < position("## Day 25\n"));
+        assert!(position("## Day 25\n") < position("## Input\n"));
+        assert!(readme.contains("stdin or `--inline`.\n\n## Day 25\n\n**Input:**  \n"));
+        assert!(read("day_25/src/lib.rs").contains("impl Solution for Day25 {"));
+        assert!(!read("day_25/src/main.rs").contains("{{"));
+        assert_eq!(read("day_25/src/test-input.txt"), "");
+
+        assert_eq!(
+            scaffold(&root, Puzzle::new(2022, 25)),
+            Err(format!("{} already exists", root.join("day_25").display()))
+        );
+        assert_eq!(
+            scaffold(&root, Puzzle::new(2022, 4)),
+            Err(format!(
+                "Day 4 is already listed in {}",
+                root.join("answers/2022.txt").display()
+            ))
+        );
+        fs::remove_dir_all(root.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_writes_nothing_for_unexpected_layout() {
+        let root = workspace("layout");
+        write(&root.join("aoc/src/solutions.rs"), "// no table\n").unwrap();
+        let answers = fs::read_to_string(root.join("answers/2022.txt")).unwrap();
+
+        assert_eq!(
+            scaffold(&root, Puzzle::new(2022, 25)),
+            Err(format!(
+                "{}: cannot find where to register the day",
+                root.join("aoc/src/solutions.rs").display()
+            ))
+        );
+        assert!(!root.join("day_25").exists());
+        assert!(!fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("day_25"));
+        assert_eq!(
+            fs::read_to_string(root.join("answers/2022.txt")).unwrap(),
+            answers
+        );
+        fs::remove_dir_all(root.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_starts_new_year() {
+        let root = workspace("year");
+        scaffold(&root, Puzzle::new(2023, 1)).unwrap();
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("aoc/Cargo.toml").contains("y2023_day_1 = { path = \"../y2023_day_1\" }\n"));
+        assert!(read("aoc/src/solutions.rs")
+            .contains("    (Puzzle::new(2023, 1), registered::<y2023_day_1::Day1>()),\n];"));
+        assert_eq!(
+            read("answers/2023.txt"),
+            format!("{}1 ? ?\n", ANSWERS_HEADER)
+        );
+        scaffold(&root, Puzzle::new(2023, 2)).unwrap();
+        let readme = read("../README.md");
+        let position = |heading: &str| readme.find(heading).unwrap();
+        assert!(position("## Day 4\n") < position("## 2023 Day 1\n"));
+        assert!(position("## 2023 Day 1\n") < position("## 2023 Day 2\n"));
+        assert!(position("## 2023 Day 2\n") < position("## Input\n"));
+        assert!(read("y2023_day_1/src/main.rs").contains("use y2023_day_1::Day1;"));
+        fs::remove_dir_all(root.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Applies `work` to every item on up to `threads` threads, returning the results in the order
+/// of the items whichever thread finishes first.
+///
+/// Threads take the next item as soon as they are done with one, so a few slow items do not
+/// hold back the others.
+pub fn map_ordered<T, R, F>(items: &[T], threads: usize, work: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result = work(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+/// Number of threads to use when none is asked for.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// CPU time used so far by the calling thread, which unlike the time elapsed does not count
+/// the time the thread spent waiting for a CPU. Only known on unix.
+#[cfg(unix)]
+pub fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Cannot fail, the clock exists and the pointer is valid
+    unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+/// CPU time used so far by the calling thread. Only known on unix.
+#[cfg(not(unix))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_results_keep_order_of_items() {
+        let items = (0..50u64).collect::<Vec<u64>>();
+        // Earlier items take longer, so they finish last
+        let squares = map_ordered(&items, 8, |&item| {
+            thread::sleep(Duration::from_micros(50 * (50 - item)));
+            item * item
+        });
+        assert_eq!(
+            squares,
+            items.iter().map(|item| item * item).collect::<Vec<u64>>()
+        );
+        assert_eq!(map_ordered(&items[..0], 4, |&item| item), Vec::<u64>::new());
+        assert_eq!(map_ordered(&items[..3], 0, |&item| item), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_items_run_concurrently() {
+        let running = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+        map_ordered(&[(); 8], 4, |_| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(20));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert!(most.load(Ordering::SeqCst) > 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_thread_cpu_time_skips_sleeping() {
+        let start = thread_cpu_time().unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert!(thread_cpu_time().unwrap() - start < Duration::from_millis(25));
+    }
+
+    #[cfg(not(unix))]
+    #[test]
+    fn test_thread_cpu_time_is_unknown() {
+        assert_eq!(thread_cpu_time(), None);
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use crate::args::Args;
+
+/// Year the runner uses when none is given, the one of the first days in the repository.
+pub const DEFAULT_YEAR: u32 = 2022;
+
+/// Identifies a puzzle, as solutions of several years live in the same workspace.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl Puzzle {
+    pub const fn new(year: u32, day: u32) -> Puzzle {
+        Puzzle { year, day }
+    }
+
+    /// Puzzle given with `--year`, defaulting to [`DEFAULT_YEAR`], and `--day`.
+    pub fn from_args(args: &Args) -> Result<Puzzle, String> {
+        let year = args.parse::<u32>("--year")?.unwrap_or(DEFAULT_YEAR);
+        Ok(Puzzle::new(year, args.required::<u32>("--day")?))
+    }
+
+    /// Crate holding the solution. Days of 2022 predate other years and keep their names.
+    pub fn crate_name(&self) -> String {
+        match self.year {
+            DEFAULT_YEAR => format!("day_{}", self.day),
+            year => format!("y{}_day_{}", year, self.day),
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(Puzzle::new(2022, 4).crate_name(), "day_4");
+        assert_eq!(Puzzle::new(2023, 12).crate_name(), "y2023_day_12");
+        assert_eq!(Puzzle::new(2023, 12).to_string(), "2023 day 12");
+    }
+}
@@ -0,0 +1,215 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::solutions::PartResult;
+
+/// Output format of the results, `text` for people and `json` or `csv` for tools.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format '{}'", format)),
+        }
+    }
+}
+
+/// Renders the results, one line or record per part. Times are given in nanoseconds in
+/// `json` and `csv`, so the fields and their order stay the same between releases; fields
+/// added later, like the year, come after the existing ones.
+pub fn render(results: &[PartResult], format: Format) -> String {
+    match format {
+        Format::Text => results
+            .iter()
+            .map(|result| {
+                format!(
+                    "{} part {}: {} (parse {:?}, solve {:?})\n",
+                    result.puzzle, result.part, result.answer, result.parse_time, result.solve_time
+                )
+            })
+            .collect(),
+        Format::Json => {
+            let records = results
+                .iter()
+                .map(|result| {
+                    format!(
+                        "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_time_ns\":{},\"solve_time_ns\":{},\"year\":{}}}",
+                        result.puzzle.day,
+                        result.part,
+                        json_string(&result.answer),
+                        result.parse_time.as_nanos(),
+                        result.solve_time.as_nanos(),
+                        result.puzzle.year
+                    )
+                })
+                .collect::<Vec<String>>();
+            format!("[{}]\n", records.join(","))
+        }
+        Format::Csv => {
+            let mut output = String::from("day,part,answer,parse_time_ns,solve_time_ns,year\n");
+            for result in results {
+                output.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    result.puzzle.day,
+                    result.part,
+                    csv_field(&result.answer),
+                    result.parse_time.as_nanos(),
+                    result.solve_time.as_nanos(),
+                    result.puzzle.year
+                ));
+            }
+            output
+        }
+    }
+}
+
+/// Compares the wall-clock time of a run with the CPU time its threads spent parsing and
+/// solving, so the ratio shows how much the threads gained over solving one part after another.
+/// Without the CPU time, as on platforms that do not measure it per thread, only the wall-clock
+/// time is given.
+pub fn summary(
+    results: &[PartResult],
+    threads: usize,
+    wall_clock: Duration,
+    cpu_time: Option<Duration>,
+) -> String {
+    let mut puzzles = results
+        .iter()
+        .map(|result| result.puzzle)
+        .collect::<Vec<_>>();
+    puzzles.dedup();
+    let summary = format!(
+        "{} parts of {} puzzles on {} threads: {:?} wall-clock",
+        results.len(),
+        puzzles.len(),
+        threads,
+        wall_clock
+    );
+    match cpu_time {
+        Some(cpu_time) => format!(
+            "{}, {:?} CPU time ({:.1}x)",
+            summary,
+            cpu_time,
+            cpu_time.as_secs_f64() / wall_clock.as_secs_f64().max(f64::MIN_POSITIVE)
+        ),
+        None => summary,
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            control if control.is_control() => {
+                output.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            other => output.push(other),
+        }
+    }
+    output.push('"');
+    output
+}
+
+// Quoted only when needed, as described in RFC 4180
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::puzzle::Puzzle;
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                puzzle: Puzzle::new(2022, 4),
+                part: 1,
+                answer: "2".to_string(),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(20),
+            },
+            PartResult {
+                puzzle: Puzzle::new(2022, 4),
+                part: 2,
+                answer: "say \"hi\", ok".to_string(),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(30),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_json() {
+        assert_eq!(
+            render(&results(), Format::Json),
+            "[{\"day\":4,\"part\":1,\"answer\":\"2\",\"parse_time_ns\":1500,\"solve_time_ns\":20,\"year\":2022},\
+             {\"day\":4,\"part\":2,\"answer\":\"say \\\"hi\\\", ok\",\"parse_time_ns\":1500,\"solve_time_ns\":30,\"year\":2022}]\n"
+        );
+        assert_eq!(render(&[], Format::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            render(&results(), Format::Csv),
+            "day,part,answer,parse_time_ns,solve_time_ns,year\n\
+             4,1,2,1500,20,2022\n\
+             4,2,\"say \"\"hi\"\", ok\",1500,30,2022\n"
+        );
+    }
+
+    #[test]
+    fn test_render_text() {
+        assert_eq!(
+            render(&results()[..1], Format::Text),
+            "2022 day 4 part 1: 2 (parse 1.5µs, solve 20ns)\n"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary(
+                &results(),
+                2,
+                Duration::from_nanos(1535),
+                Some(Duration::from_nanos(3070))
+            ),
+            "2 parts of 1 puzzles on 2 threads: 1.535µs wall-clock, 3.07µs CPU time (2.0x)"
+        );
+        assert_eq!(
+            summary(&results(), 2, Duration::from_nanos(1535), None),
+            "2 parts of 1 puzzles on 2 threads: 1.535µs wall-clock"
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!(
+            "xml".parse::<Format>(),
+            Err("Unknown format 'xml'".to_string())
+        );
+    }
+}
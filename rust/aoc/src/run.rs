@@ -0,0 +1,109 @@
+use std::fs;
+use std::time::Instant;
+
+use crate::args::Args;
+use crate::input::{committed_or_provided, default_cache_dir, HttpFetcher, InputProvider};
+use crate::new::workspace_root;
+use crate::pool::{default_threads, map_ordered, thread_cpu_time};
+use crate::puzzle::Puzzle;
+use crate::report::{render, summary, Format};
+use crate::solutions::{puzzles, run_day, solve};
+
+pub const USAGE: &str = "aoc run [--year N] --day N [--input PATH] [--format text|json|csv]
+  aoc run --all [--year N] [--threads N] [--format text|json|csv]";
+
+/// Solves both parts of a puzzle and prints the answers with their timings.
+pub fn run(args: &Args) -> Result<(), String> {
+    let format = args.parse::<Format>("--format")?.unwrap_or(Format::Text);
+    if args.flag("--all") {
+        return run_all(args, format);
+    }
+
+    let puzzle = Puzzle::from_args(args)?;
+    print!(
+        "{}",
+        render(&run_day(puzzle, &load_input(args, puzzle)?)?, format)
+    );
+    Ok(())
+}
+
+/// Solves every part of every puzzle, of one year if given, on a pool of threads.
+///
+/// Every puzzle is a single job, parsed once by the thread that then solves its parts one after
+/// the other, so only different puzzles are solved in parallel, never the parts of one. Results
+/// are printed in the order of the puzzles, followed by the time taken from start to end against
+/// the CPU time of all threads together where it is known. The summary goes to stderr for `json`
+/// and `csv`, so the output stays machine readable.
+fn run_all(args: &Args, format: Format) -> Result<(), String> {
+    let year = args.parse::<u32>("--year")?;
+    let threads = args
+        .parse::<usize>("--threads")?
+        .unwrap_or_else(default_threads);
+    let puzzles = puzzles()
+        .into_iter()
+        .filter(|puzzle| year.is_none_or(|year| puzzle.year == year))
+        .collect::<Vec<Puzzle>>();
+
+    let provider = InputProvider::new(&default_cache_dir()?, HttpFetcher::from_env());
+    let inputs = puzzles
+        .iter()
+        .map(|&puzzle| committed_or_provided(&workspace_root(), &provider, puzzle))
+        .collect::<Result<Vec<String>, String>>()?;
+    let jobs = puzzles
+        .iter()
+        .zip(&inputs)
+        .map(|(&puzzle, input)| (puzzle, input.as_str()))
+        .collect::<Vec<(Puzzle, &str)>>();
+
+    let start = Instant::now();
+    let solved = map_ordered(&jobs, threads, |&(puzzle, input)| {
+        let cpu_start = thread_cpu_time();
+        let results = solve(puzzle, input, &[1, 2]);
+        let cpu_time = cpu_start
+            .zip(thread_cpu_time())
+            .map(|(start, end)| end - start);
+        (results, cpu_time)
+    });
+    let wall_clock = start.elapsed();
+    let cpu_time = solved.iter().map(|(_, cpu_time)| *cpu_time).sum();
+    let mut results = Vec::new();
+    for (solved, _) in solved {
+        results.extend(solved?);
+    }
+
+    let summary = summary(&results, threads, wall_clock, cpu_time);
+    print!("{}", render(&results, format));
+    match format {
+        Format::Text => println!("{}", summary),
+        Format::Json | Format::Csv => eprintln!("{}", summary),
+    }
+    Ok(())
+}
+
+/// Input given with `--input`, otherwise the one committed in the crate of the puzzle, otherwise
+/// the one in the cache, downloaded if not there yet.
+pub fn load_input(args: &Args, puzzle: Puzzle) -> Result<String, String> {
+    match args.value("--input") {
+        Some(path) => fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error)),
+        None => committed_or_provided(
+            &workspace_root(),
+            &InputProvider::new(&default_cache_dir()?, HttpFetcher::from_env()),
+            puzzle,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_load_input_reads_committed_input() {
+        let args = Args::new(&["--day".to_string(), "1".to_string()]);
+        assert_eq!(
+            load_input(&args, Puzzle::new(2022, 1)),
+            Ok(fs::read_to_string(workspace_root().join("day_1/src/input")).unwrap())
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::{Rng, Solution};
+
+use crate::puzzle::Puzzle;
+
+/// Answer to one part of a puzzle, with the time spent parsing the input and solving the part.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PartResult {
+    pub puzzle: Puzzle,
+    pub part: u32,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+// Solves the given parts of a puzzle, after parsing the input once
+type Solver = fn(Puzzle, &str, &[u32]) -> Result<Vec<PartResult>, String>;
+type Generator = fn(usize, &mut Rng) -> Option<String>;
+type Oracle = fn(&str) -> Option<Result<[String; 2], String>>;
+
+// Everything the runner and the differential tests need from a day
+struct Registered {
+    solve: Solver,
+    generate: Generator,
+    oracle: Oracle,
+}
+
+const fn registered<S: Solution>() -> Registered {
+    Registered {
+        solve: timed::<S>,
+        generate: S::generate,
+        oracle: S::oracle,
+    }
+}
+
+/// Every puzzle the runner can solve, in order.
+const SOLUTIONS: &[(Puzzle, Registered)] = &[
+    (Puzzle::new(2022, 1), registered::<day_1::Day1>()),
+    (Puzzle::new(2022, 2), registered::<day_2::Day2>()),
+    (Puzzle::new(2022, 3), registered::<day_3::Day3>()),
+    (Puzzle::new(2022, 4), registered::<day_4::Day4>()),
+];
+
+pub fn puzzles() -> Vec<Puzzle> {
+    SOLUTIONS.iter().map(|(puzzle, _)| *puzzle).collect()
+}
+
+fn find(puzzle: Puzzle) -> Option<&'static Registered> {
+    SOLUTIONS
+        .iter()
+        .find(|(registered, _)| *registered == puzzle)
+        .map(|(_, registered)| registered)
+}
+
+/// Parses the input once and solves the given parts, sharing the parse time between them.
+pub fn solve(puzzle: Puzzle, input: &str, parts: &[u32]) -> Result<Vec<PartResult>, String> {
+    let registered = find(puzzle).ok_or_else(|| format!("No solution for {}", puzzle))?;
+    (registered.solve)(puzzle, input, parts)
+}
+
+/// Random valid input of `size` items, from the generator of the day.
+pub fn generate(puzzle: Puzzle, size: usize, rng: &mut Rng) -> Result<String, String> {
+    find(puzzle)
+        .and_then(|registered| (registered.generate)(size, rng))
+        .ok_or_else(|| format!("No generator for {}", puzzle))
+}
+
+/// Answers of the naive reference implementation, which the solutions are checked against.
+pub fn oracle_answers(puzzle: Puzzle, input: &str) -> Result<[String; 2], String> {
+    find(puzzle)
+        .and_then(|registered| (registered.oracle)(input))
+        .ok_or_else(|| format!("No oracle for {}", puzzle))?
+}
+
+/// Parses the input once and solves both parts.
+pub fn run_day(puzzle: Puzzle, input: &str) -> Result<[PartResult; 2], String> {
+    Ok(solve(puzzle, input, &[1, 2])?.try_into().unwrap())
+}
+
+/// Answers to both parts, as computed by the solution of the day.
+pub fn answers(puzzle: Puzzle, input: &str) -> Result<[String; 2], String> {
+    Ok(run_day(puzzle, input)?.map(|result| result.answer))
+}
+
+fn timed<S: Solution>(
+    puzzle: Puzzle,
+    input: &str,
+    parts: &[u32],
+) -> Result<Vec<PartResult>, String> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let solve = match part {
+                1 => S::part_one,
+                2 => S::part_two,
+                _ => return Err(format!("{} has no part {}", puzzle, part)),
+            };
+            let start = Instant::now();
+            let answer = solve(&parsed);
+            Ok(PartResult {
+                puzzle,
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const EXAMPLES: [(u32, &str, [&str; 2]); 4] = [
+        (
+            1,
+            "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+            ["24000", "45000"],
+        ),
+        (2, "A Y\nB X\nC Z\n", ["15", "12"]),
+        (
+            3,
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n\
+             wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n",
+            ["157", "70"],
+        ),
+        (
+            4,
+            "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n",
+            ["2", "4"],
+        ),
+    ];
+
+    #[test]
+    fn test_answers_of_examples() {
+        for (day, input, expected) in EXAMPLES {
+            assert_eq!(
+                answers(Puzzle::new(2022, day), input),
+                Ok(expected.map(String::from))
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_day_numbers_the_parts() {
+        let puzzle = Puzzle::new(2022, 2);
+        let [part_one, part_two] = run_day(puzzle, "A Y\nB X\nC Z\n").unwrap();
+        assert_eq!((part_one.puzzle, part_one.part), (puzzle, 1));
+        assert_eq!((part_two.puzzle, part_two.part), (puzzle, 2));
+        assert_eq!(part_one.parse_time, part_two.parse_time);
+        assert_eq!(
+            run_day(puzzle, "A Y\nA Q\n"),
+            Err("line 2, column 3: expected X, Y or Z, found 'Q'".to_string())
+        );
+        assert_eq!(
+            run_day(Puzzle::new(2021, 2), ""),
+            Err("No solution for 2021 day 2".to_string())
+        );
+    }
+}
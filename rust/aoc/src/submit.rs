@@ -0,0 +1,515 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::args::Args;
+use crate::input::{default_cache_dir, USER_AGENT};
+use crate::puzzle::Puzzle;
+use crate::run::load_input;
+use crate::solutions::run_day;
+
+pub const USAGE: &str = "aoc submit [--year N] --day N --part 1|2 [--answer X] [--input PATH]";
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted during a cooldown, the answer was not checked.
+    TooSoon,
+    /// The part was solved before, the answer was not checked.
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::TooSoon => "too-soon",
+            Verdict::AlreadySolved => "already-solved",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(verdict: &str) -> Result<Verdict, String> {
+        match verdict {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "too-soon" => Ok(Verdict::TooSoon),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            _ => Err(format!("Unknown verdict '{}'", verdict)),
+        }
+    }
+}
+
+/// Verdict on a submitted answer, and the seconds to wait before the next submission.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait: u64,
+}
+
+/// Checks answers, either on the puzzle site or on a local stand-in.
+pub trait Submitter {
+    fn submit(&self, puzzle: Puzzle, part: u32, answer: &str) -> Result<Response, String>;
+}
+
+/// Posts answers to the puzzle site, authenticated by the session cookie of the user.
+pub struct HttpSubmitter {
+    base_url: String,
+    session: String,
+}
+
+impl HttpSubmitter {
+    pub fn new(base_url: &str, session: &str) -> HttpSubmitter {
+        HttpSubmitter {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Uses `AOC_SESSION` for the cookie and `AOC_URL` to point at another server.
+    pub fn from_env() -> Result<HttpSubmitter, String> {
+        let base_url =
+            std::env::var("AOC_URL").unwrap_or_else(|_| "https://adventofcode.com".to_string());
+        let session = std::env::var("AOC_SESSION")
+            .map_err(|_| "Set AOC_SESSION to submit answers".to_string())?;
+        Ok(HttpSubmitter::new(&base_url, &session))
+    }
+}
+
+impl Submitter for HttpSubmitter {
+    fn submit(&self, puzzle: Puzzle, part: u32, answer: &str) -> Result<Response, String> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url, puzzle.year, puzzle.day
+        );
+        let page = ureq::post(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|error| format!("{}: {}", url, error))?;
+        parse_response(&page).ok_or_else(|| format!("{}: unexpected response", url))
+    }
+}
+
+/// Reads the verdict from the page returned for a submitted answer.
+pub fn parse_response(page: &str) -> Option<Response> {
+    let page = &page.to_lowercase();
+    let response = |verdict, wait| Some(Response { verdict, wait });
+    if page.contains("that's the right answer") {
+        response(Verdict::Correct, 0)
+    } else if page.contains("you gave an answer too recently") {
+        response(Verdict::TooSoon, wait_left(page).unwrap_or(60))
+    } else if page.contains("you don't seem to be solving the right level") {
+        response(Verdict::AlreadySolved, 0)
+    } else if page.contains("that's not the right answer") {
+        let verdict = if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        response(verdict, wrong_answer_wait(page).unwrap_or(60))
+    } else {
+        None
+    }
+}
+
+// "you have 4m 51s left to wait"
+fn wait_left(page: &str) -> Option<u64> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("you have ")? + "you have ".len();
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            if let Some(minutes) = amount.strip_suffix('m') {
+                minutes.parse::<u64>().ok().map(|minutes| minutes * 60)
+            } else {
+                amount.strip_suffix('s')?.parse::<u64>().ok()
+            }
+        })
+        .sum()
+}
+
+// "please wait one minute before trying again", or "wait 5 minutes"
+fn wrong_answer_wait(page: &str) -> Option<u64> {
+    let start = page.find("please wait ")? + "please wait ".len();
+    let amount = page[start..].split_whitespace().next()?;
+    match amount {
+        "one" => Some(60),
+        minutes => minutes.parse::<u64>().ok().map(|minutes| minutes * 60),
+    }
+}
+
+/// Submitted answer and the response to it, as stored in the history.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Submission {
+    pub time: u64,
+    pub puzzle: Puzzle,
+    pub part: u32,
+    pub answer: String,
+    pub response: Response,
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.time,
+            self.puzzle.year,
+            self.puzzle.day,
+            self.part,
+            self.answer,
+            self.response.verdict,
+            self.response.wait
+        )
+    }
+}
+
+impl FromStr for Submission {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Submission, String> {
+        let fields = line.split('\t').collect::<Vec<&str>>();
+        if fields.len() != 7 {
+            return Err(format!("Invalid submission '{}'", line));
+        }
+        let number = |field: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid number '{}' in submission '{}'", field, line))
+        };
+        Ok(Submission {
+            time: number(fields[0])?,
+            puzzle: Puzzle::new(number(fields[1])? as u32, number(fields[2])? as u32),
+            part: number(fields[3])? as u32,
+            answer: fields[4].to_string(),
+            response: Response {
+                verdict: fields[5].parse()?,
+                wait: number(fields[6])?,
+            },
+        })
+    }
+}
+
+/// Submits answers through a backend and keeps every submission in a history file, one
+/// tab separated line each.
+///
+/// The history is used to refuse answers that cannot be right, because the same answer or a
+/// bound on it was already rejected, and answers given before the cooldown of the last answer
+/// to the same part ended.
+pub struct SubmissionClient<S: Submitter> {
+    submitter: S,
+    history_path: PathBuf,
+}
+
+impl<S: Submitter> SubmissionClient<S> {
+    pub fn new(submitter: S, history_path: &Path) -> SubmissionClient<S> {
+        SubmissionClient {
+            submitter,
+            history_path: history_path.to_path_buf(),
+        }
+    }
+
+    pub fn history(&self) -> Result<Vec<Submission>, String> {
+        if !self.history_path.exists() {
+            return Ok(Vec::new());
+        }
+        fs::read_to_string(&self.history_path)
+            .map_err(|error| format!("{}: {}", self.history_path.display(), error))?
+            .lines()
+            .map(Submission::from_str)
+            .collect()
+    }
+
+    /// Submits the answer at `now`, in seconds since the Unix epoch, unless the history
+    /// already tells the verdict or a cooldown is still running.
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u32,
+        answer: &str,
+        now: u64,
+    ) -> Result<Response, String> {
+        if answer.is_empty() || answer.contains(['\t', '\n', '\r']) {
+            return Err(format!("Invalid answer '{}'", answer));
+        }
+
+        let history = self.history()?;
+        let previous = history
+            .iter()
+            .filter(|submission| (submission.puzzle, submission.part) == (puzzle, part))
+            .collect::<Vec<&Submission>>();
+        for submission in &previous {
+            check_against(submission, answer)?;
+            if submission.response.verdict == Verdict::Correct {
+                return Ok(submission.response);
+            }
+        }
+
+        // Every part of every puzzle has its own cooldown
+        if let Some(last) = previous.last() {
+            let ready = last.time + last.response.wait;
+            if ready > now {
+                return Err(format!("Wait {}s before submitting again", ready - now));
+            }
+        }
+
+        let response = self.submitter.submit(puzzle, part, answer)?;
+        self.record(&Submission {
+            time: now,
+            puzzle,
+            part,
+            answer: answer.to_string(),
+            response,
+        })?;
+        Ok(response)
+    }
+
+    fn record(&self, submission: &Submission) -> Result<(), String> {
+        let describe =
+            |error: std::io::Error| format!("{}: {}", self.history_path.display(), error);
+        if let Some(directory) = self.history_path.parent() {
+            fs::create_dir_all(directory).map_err(describe)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.history_path)
+            .map_err(describe)?;
+        writeln!(file, "{}", submission).map_err(describe)
+    }
+}
+
+// Errors if the earlier submission proves the answer wrong
+fn check_against(submission: &Submission, answer: &str) -> Result<(), String> {
+    let known = &submission.answer;
+    let numbers = (answer.parse::<i128>().ok(), known.parse::<i128>().ok());
+    let refused = match submission.response.verdict {
+        Verdict::Correct => known != answer,
+        Verdict::Wrong => known == answer,
+        Verdict::TooHigh => {
+            known == answer || matches!(numbers, (Some(answer), Some(known)) if answer > known)
+        }
+        Verdict::TooLow => {
+            known == answer || matches!(numbers, (Some(answer), Some(known)) if answer < known)
+        }
+        Verdict::TooSoon | Verdict::AlreadySolved => false,
+    };
+    match refused {
+        true => Err(format!(
+            "{} part {} was answered {} before, which is {}",
+            submission.puzzle, submission.part, known, submission.response.verdict
+        )),
+        false => Ok(()),
+    }
+}
+
+/// Submits the given answer, or the one computed from the input, and prints the verdict.
+pub fn run(args: &Args) -> Result<(), String> {
+    let puzzle = Puzzle::from_args(args)?;
+    let part = args.required::<u32>("--part")?;
+    if !(1..=2).contains(&part) {
+        return Err(format!("Invalid part {}", part));
+    }
+    let answer = match args.value("--answer") {
+        Some(answer) => answer.to_string(),
+        None => {
+            let results = run_day(puzzle, &load_input(args, puzzle)?)?;
+            results[part as usize - 1].answer.clone()
+        }
+    };
+
+    let client = SubmissionClient::new(
+        HttpSubmitter::from_env()?,
+        &default_cache_dir()?.join("submissions.tsv"),
+    );
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|error| error.to_string())?
+        .as_secs();
+    let response = client.submit(puzzle, part, &answer, now)?;
+    println!(
+        "{} part {}: {} is {}",
+        puzzle, part, answer, response.verdict
+    );
+    if response.wait > 0 {
+        println!("Next submission possible in {}s", response.wait);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+
+    use super::*;
+    use crate::stub::StubServer;
+
+    const DAY_4: Puzzle = Puzzle { year: 2022, day: 4 };
+
+    const RIGHT: &str =
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+                            Please wait one minute before trying again.</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after \
+                            submitting an answer before trying again. You have 4m 51s left to wait.</p></article>";
+
+    fn history_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{}-{}.tsv", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    // Answers 441 for part one, and too high for anything else
+    fn server() -> StubServer {
+        StubServer::start(|request| match request.body.as_str() {
+            "level=1&answer=441" => (200, RIGHT.to_string()),
+            _ => (200, TOO_HIGH.to_string()),
+        })
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = |verdict, wait| Some(Response { verdict, wait });
+        assert_eq!(parse_response(RIGHT), response(Verdict::Correct, 0));
+        assert_eq!(parse_response(TOO_HIGH), response(Verdict::TooHigh, 60));
+        assert_eq!(parse_response(TOO_SOON), response(Verdict::TooSoon, 291));
+        assert_eq!(
+            parse_response(
+                "That's not the right answer. Please wait 5 minutes before trying again."
+            ),
+            response(Verdict::Wrong, 300)
+        );
+        assert_eq!(parse_response("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_wait_left_ignores_unexpected_text() {
+        assert_eq!(wait_left("you have 4m 51s left to wait"), Some(291));
+        assert_eq!(wait_left("you have 30s left to wait"), Some(30));
+        assert_eq!(wait_left("you have 4m 5é left to wait"), None);
+        assert_eq!(wait_left("you have a moment left to wait"), None);
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let submission = Submission {
+            time: 1670130000,
+            puzzle: Puzzle::new(2022, 4),
+            part: 2,
+            answer: "861".to_string(),
+            response: Response {
+                verdict: Verdict::TooLow,
+                wait: 60,
+            },
+        };
+        assert_eq!(
+            submission.to_string(),
+            "1670130000\t2022\t4\t2\t861\ttoo-low\t60"
+        );
+        assert_eq!(submission.to_string().parse(), Ok(submission));
+    }
+
+    #[test]
+    fn test_correct_answer_is_not_submitted_again() {
+        let server = server();
+        let path = history_path("correct");
+        let client = SubmissionClient::new(HttpSubmitter::new(&server.url(), "secret"), &path);
+
+        let correct = Response {
+            verdict: Verdict::Correct,
+            wait: 0,
+        };
+        assert_eq!(client.submit(DAY_4, 1, "441", 100), Ok(correct));
+        assert_eq!(client.submit(DAY_4, 1, "441", 200), Ok(correct));
+        assert_eq!(
+            client.submit(DAY_4, 1, "442", 200),
+            Err("2022 day 4 part 1 was answered 441 before, which is correct".to_string())
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/4/answer");
+        assert_eq!(
+            requests[0].header("cookie"),
+            Some("session=secret".to_string())
+        );
+        assert_eq!(client.history().unwrap().len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_answers_and_cooldown() {
+        let server = server();
+        let path = history_path("wrong");
+        let client = SubmissionClient::new(HttpSubmitter::new(&server.url(), "secret"), &path);
+
+        assert_eq!(
+            client
+                .submit(DAY_4, 2, "900", 100)
+                .map(|response| response.verdict),
+            Ok(Verdict::TooHigh)
+        );
+        assert_eq!(
+            client.submit(DAY_4, 2, "870", 130),
+            Err("Wait 30s before submitting again".to_string())
+        );
+        assert_eq!(
+            client.submit(DAY_4, 2, "900", 200),
+            Err("2022 day 4 part 2 was answered 900 before, which is too-high".to_string())
+        );
+        assert!(client.submit(DAY_4, 2, "901", 200).is_err());
+        assert!(client.submit(DAY_4, 2, "870", 200).is_ok());
+
+        assert_eq!(server.requests().len(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_cooldown_is_kept_per_part() {
+        let server = server();
+        let path = history_path("cooldown");
+        let client = SubmissionClient::new(HttpSubmitter::new(&server.url(), "secret"), &path);
+        let day_3 = Puzzle::new(2022, 3);
+
+        assert!(client.submit(DAY_4, 2, "900", 100).is_ok());
+        assert_eq!(
+            client
+                .submit(day_3, 2, "900", 110)
+                .map(|response| response.verdict),
+            Ok(Verdict::TooHigh)
+        );
+        assert!(client.submit(DAY_4, 1, "441", 120).is_ok());
+        assert_eq!(
+            client.submit(DAY_4, 2, "870", 130),
+            Err("Wait 30s before submitting again".to_string())
+        );
+        assert_eq!(
+            client.submit(day_3, 2, "870", 130),
+            Err("Wait 40s before submitting again".to_string())
+        );
+
+        assert_eq!(server.requests().len(), 3);
+        fs::remove_file(&path).unwrap();
+    }
+}
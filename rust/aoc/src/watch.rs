@@ -0,0 +1,469 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::answers::{answers_path, parse_answers};
+use crate::args::Args;
+use crate::input::{committed_input, default_cache_dir, HttpFetcher, InputProvider};
+use crate::new::workspace_root;
+use crate::puzzle::Puzzle;
+
+pub const USAGE: &str = "aoc watch [--year N] --day N [--input PATH]";
+
+// Delay between two looks at the watched files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Outcome of the tests of the crate of a puzzle.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Tests {
+    Passed(usize),
+    Failed { passed: usize, failed: usize },
+    BuildFailed,
+}
+
+impl std::fmt::Display for Tests {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Tests::Passed(passed) => write!(f, "{} passed", passed),
+            Tests::Failed { passed, failed } => write!(f, "{} failed, {} passed", failed, passed),
+            Tests::BuildFailed => write!(f, "build failed"),
+        }
+    }
+}
+
+/// Answer to one part, the one expected if known, and the time taken to parse and solve.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PartCheck {
+    pub part: u32,
+    pub answer: String,
+    pub expected: Option<String>,
+    pub time: Duration,
+}
+
+/// Result of rebuilding a puzzle, running its tests and solving its input.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Check {
+    pub tests: Tests,
+    pub parts: Result<Vec<PartCheck>, String>,
+}
+
+/// Checks the puzzle again whenever a source file of its crate, in any directory below `src`,
+/// its manifest or its input changes.
+pub fn run(args: &Args) -> Result<(), String> {
+    let puzzle = Puzzle::from_args(args)?;
+    let root = workspace_root();
+    let crate_dir = root.join(puzzle.crate_name());
+    if !crate_dir.exists() {
+        return Err(format!("{} does not exist", crate_dir.display()));
+    }
+    let working_dir = env::current_dir().map_err(|error| error.to_string())?;
+    let input = input_path(args, puzzle, &crate_dir, &working_dir)?;
+
+    let mut previous: Option<Check> = None;
+    let mut seen = BTreeMap::new();
+    loop {
+        let current = snapshot(&crate_dir, &input);
+        let changed = changed_files(&seen, &current);
+        if !changed.is_empty() {
+            if !seen.is_empty() {
+                let names = changed
+                    .iter()
+                    .map(|path| {
+                        path.strip_prefix(&root)
+                            .unwrap_or(path)
+                            .display()
+                            .to_string()
+                    })
+                    .collect::<Vec<String>>();
+                println!("Changed: {}", names.join(", "));
+            }
+            let check = check(&root, puzzle, &input)?;
+            print!("{}", render(previous.as_ref(), &check));
+            previous = Some(check);
+            seen = current;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// The input given, the one committed in the crate, or the cached one. A relative input is
+// resolved against `working_dir` once, since the runner is started from the workspace root.
+fn input_path(
+    args: &Args,
+    puzzle: Puzzle,
+    crate_dir: &Path,
+    working_dir: &Path,
+) -> Result<PathBuf, String> {
+    if let Some(path) = args.value("--input") {
+        return Ok(working_dir.join(path));
+    }
+    if let Some(path) = committed_input(crate_dir) {
+        return Ok(path);
+    }
+    let provider = InputProvider::new(&default_cache_dir()?, HttpFetcher::from_env());
+    provider.input(puzzle)?;
+    Ok(provider.path(puzzle))
+}
+
+// Modification time of every file below the source directory of the crate, of its manifest
+// and of the input
+fn snapshot(crate_dir: &Path, input: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = vec![crate_dir.join("Cargo.toml"), input.to_path_buf()];
+    files_below(&crate_dir.join("src"), &mut files);
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+            modified.ok().map(|modified| (path, modified))
+        })
+        .collect()
+}
+
+// Symbolic links to directories are not followed, so a link back up cannot loop
+fn files_below(directory: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(directory).into_iter().flatten().flatten() {
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => files_below(&entry.path(), files),
+            _ => files.push(entry.path()),
+        }
+    }
+}
+
+fn changed_files(
+    before: &BTreeMap<PathBuf, SystemTime>,
+    after: &BTreeMap<PathBuf, SystemTime>,
+) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<PathBuf>>();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed
+}
+
+// Runs the tests of the crate, then the rebuilt runner on the input
+fn check(root: &Path, puzzle: Puzzle, input: &Path) -> Result<Check, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let tests = Command::new(&cargo)
+        .current_dir(root)
+        .args(["test", "-q", "-p", &puzzle.crate_name()])
+        .output()
+        .map_err(|error| format!("{}: {}", cargo, error))?;
+    let tests = test_outcome(
+        &String::from_utf8_lossy(&tests.stdout),
+        tests.status.success(),
+    );
+
+    let run = runner(&cargo, root, puzzle, input)
+        .output()
+        .map_err(|error| format!("{}: {}", cargo, error))?;
+    let parts = match run.status.success() {
+        true => parse_csv(&String::from_utf8_lossy(&run.stdout)),
+        false => Err(last_line(&String::from_utf8_lossy(&run.stderr))),
+    };
+
+    let answers = fs::read_to_string(answers_path(root, puzzle.year))
+        .ok()
+        .and_then(|answers| parse_answers(&answers).ok())
+        .and_then(|mut answers| answers.remove(&puzzle.day))
+        .unwrap_or_default();
+    let parts = parts.map(|parts| {
+        parts
+            .into_iter()
+            .map(|(part, answer, time)| PartCheck {
+                part,
+                answer,
+                expected: answers.get(part as usize - 1).cloned().flatten(),
+                time,
+            })
+            .collect()
+    });
+    Ok(Check { tests, parts })
+}
+
+// The rebuilt runner solving the input, started from the workspace root
+fn runner(cargo: &str, root: &Path, puzzle: Puzzle, input: &Path) -> Command {
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(root)
+        .args(["run", "-q", "--release", "-p", "aoc", "--", "run"])
+        .args(["--year", &puzzle.year.to_string()])
+        .args(["--day", &puzzle.day.to_string()])
+        .args(["--format", "csv", "--input"])
+        .arg(input);
+    command
+}
+
+/// Sums up the `test result:` lines of `cargo test`.
+pub fn test_outcome(output: &str, success: bool) -> Tests {
+    let count = |line: &str, label: &str| {
+        line.split(';')
+            .find_map(|field| field.trim().strip_suffix(label))
+            .and_then(|count| count.rsplit(' ').next()?.parse::<usize>().ok())
+            .unwrap_or(0)
+    };
+    let results = output
+        .lines()
+        .filter(|line| line.starts_with("test result:"))
+        .map(|line| (count(line, " passed"), count(line, " failed")))
+        .collect::<Vec<(usize, usize)>>();
+    if results.is_empty() && !success {
+        return Tests::BuildFailed;
+    }
+
+    let passed = results.iter().map(|(passed, _)| passed).sum();
+    match results.iter().map(|(_, failed)| failed).sum() {
+        0 if success => Tests::Passed(passed),
+        failed => Tests::Failed {
+            passed,
+            failed: failed.max(1),
+        },
+    }
+}
+
+// Part, answer and total time of every record written by `aoc run --format csv`
+fn parse_csv(output: &str) -> Result<Vec<(u32, String, Duration)>, String> {
+    output
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let invalid = || format!("Invalid result '{}'", line);
+            let fields = line.split(',').collect::<Vec<&str>>();
+            if fields.len() < 6 {
+                return Err(invalid());
+            }
+            let number = |field: &str| field.parse::<u64>().map_err(|_| invalid());
+            // The year comes last, after the times
+            let answer = fields[2..fields.len() - 3].join(",");
+            let answer = match answer
+                .strip_prefix('"')
+                .and_then(|quoted| quoted.strip_suffix('"'))
+            {
+                Some(quoted) => quoted.replace("\"\"", "\""),
+                None => answer,
+            };
+            let nanos = number(fields[fields.len() - 3])? + number(fields[fields.len() - 2])?;
+            Ok((
+                number(fields[1])? as u32,
+                answer,
+                Duration::from_nanos(nanos),
+            ))
+        })
+        .collect()
+}
+
+fn last_line(output: &str) -> String {
+    output.lines().last().unwrap_or("failed").to_string()
+}
+
+/// One line for the tests and one per part, with what changed since the previous check.
+pub fn render(previous: Option<&Check>, current: &Check) -> String {
+    let mut output = format!("Tests: {}", current.tests);
+    if let Some(previous) = previous.filter(|previous| previous.tests != current.tests) {
+        output.push_str(&format!(" (was {})", previous.tests));
+    }
+    output.push('\n');
+
+    let parts = match &current.parts {
+        Ok(parts) => parts,
+        Err(error) => return output + &format!("Run failed: {}\n", error),
+    };
+    for part in parts {
+        let status = match &part.expected {
+            Some(expected) if *expected == part.answer => "ok".to_string(),
+            Some(expected) => format!("FAIL, expected {}", expected),
+            None => "unknown".to_string(),
+        };
+        output.push_str(&format!(
+            "Part {}: {} {} in {:.2?}",
+            part.part, part.answer, status, part.time
+        ));
+
+        let before = previous
+            .and_then(|previous| previous.parts.as_ref().ok())
+            .and_then(|parts| parts.iter().find(|before| before.part == part.part));
+        if let Some(before) = before {
+            let change = part.time.as_secs_f64() - before.time.as_secs_f64();
+            output.push_str(&format!(" ({:+.2}ms", change * 1000.0));
+            if before.answer != part.answer {
+                output.push_str(&format!(", was {}", before.answer));
+            }
+            output.push(')');
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn part(part: u32, answer: &str, expected: Option<&str>, micros: u64) -> PartCheck {
+        PartCheck {
+            part,
+            answer: answer.to_string(),
+            expected: expected.map(String::from),
+            time: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_test_outcome() {
+        let output = "\nrunning 17 tests\n.................\n\
+                      test result: ok. 17 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n\n\
+                      test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(test_outcome(output, true), Tests::Passed(17));
+        assert_eq!(
+            test_outcome(
+                "test result: FAILED. 15 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out\n",
+                false
+            ),
+            Tests::Failed {
+                passed: 15,
+                failed: 2
+            }
+        );
+        assert_eq!(test_outcome("", false), Tests::BuildFailed);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let output = "day,part,answer,parse_time_ns,solve_time_ns,year\n\
+                      3,1,7997,1000,500,2022\n\
+                      3,2,\"a,\"\"b\"\"\",1000,2000,2022\n";
+        assert_eq!(
+            parse_csv(output),
+            Ok(vec![
+                (1, "7997".to_string(), Duration::from_nanos(1500)),
+                (2, "a,\"b\"".to_string(), Duration::from_nanos(3000)),
+            ])
+        );
+        assert!(parse_csv("header\n2022,3\n").is_err());
+    }
+
+    #[test]
+    fn test_render_first_check() {
+        let check = Check {
+            tests: Tests::Passed(17),
+            parts: Ok(vec![
+                part(1, "7997", Some("7997"), 1500),
+                part(2, "12", None, 3000),
+            ]),
+        };
+        assert_eq!(
+            render(None, &check),
+            "Tests: 17 passed\nPart 1: 7997 ok in 1.50ms\nPart 2: 12 unknown in 3.00ms\n"
+        );
+    }
+
+    #[test]
+    fn test_render_diff_with_previous_check() {
+        let previous = Check {
+            tests: Tests::Passed(17),
+            parts: Ok(vec![part(1, "7997", Some("7997"), 1500)]),
+        };
+        let current = Check {
+            tests: Tests::Failed {
+                passed: 16,
+                failed: 1,
+            },
+            parts: Ok(vec![part(1, "8000", Some("7997"), 1250)]),
+        };
+        assert_eq!(
+            render(Some(&previous), &current),
+            "Tests: 1 failed, 16 passed (was 17 passed)\n\
+             Part 1: 8000 FAIL, expected 7997 in 1.25ms (-0.25ms, was 7997)\n"
+        );
+
+        let broken = Check {
+            tests: Tests::BuildFailed,
+            parts: Err("error: could not compile `day_3`".to_string()),
+        };
+        assert_eq!(
+            render(Some(&current), &broken),
+            "Tests: build failed (was 1 failed, 16 passed)\n\
+             Run failed: error: could not compile `day_3`\n"
+        );
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let before = BTreeMap::from([
+            (PathBuf::from("src/main.rs"), time(1)),
+            (PathBuf::from("src/input.txt"), time(1)),
+            (PathBuf::from("src/old.rs"), time(1)),
+        ]);
+        let after = BTreeMap::from([
+            (PathBuf::from("src/main.rs"), time(2)),
+            (PathBuf::from("src/input.txt"), time(1)),
+            (PathBuf::from("src/new.rs"), time(1)),
+        ]);
+        assert_eq!(
+            changed_files(&before, &after),
+            vec![
+                PathBuf::from("src/main.rs"),
+                PathBuf::from("src/new.rs"),
+                PathBuf::from("src/old.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_includes_nested_sources_and_manifest() {
+        let crate_dir = env::temp_dir().join(format!("aoc-watch-crate-{}", std::process::id()));
+        fs::create_dir_all(crate_dir.join("src/solver")).unwrap();
+        for file in ["Cargo.toml", "src/lib.rs", "src/solver/mod.rs", "input.txt"] {
+            fs::write(crate_dir.join(file), "").unwrap();
+        }
+
+        let files = snapshot(&crate_dir, &crate_dir.join("input.txt"))
+            .into_keys()
+            .map(|path| path.strip_prefix(&crate_dir).unwrap().to_path_buf())
+            .collect::<Vec<PathBuf>>();
+        assert_eq!(
+            files,
+            ["Cargo.toml", "input.txt", "src/lib.rs", "src/solver/mod.rs"]
+                .map(PathBuf::from)
+                .to_vec()
+        );
+        fs::remove_dir_all(&crate_dir).unwrap();
+    }
+
+    #[test]
+    fn test_relative_input_is_watched_and_run_from_working_dir() {
+        let working_dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&working_dir).unwrap();
+        fs::write(working_dir.join("my.txt"), "2-4,6-8\n").unwrap();
+        let crate_dir = workspace_root().join("day_4");
+        let args = Args::new(&[
+            "--day".to_string(),
+            "4".to_string(),
+            "--input".to_string(),
+            "my.txt".to_string(),
+        ]);
+
+        let input = input_path(&args, Puzzle::new(2022, 4), &crate_dir, &working_dir).unwrap();
+        assert_eq!(input, working_dir.join("my.txt"));
+        assert!(snapshot(&crate_dir, &input).contains_key(&input));
+
+        // The runner is started from the root and reads the same file
+        let root = workspace_root();
+        let runner = runner("cargo", &root, Puzzle::new(2022, 4), &input);
+        assert_eq!(runner.get_current_dir(), Some(root.as_path()));
+        assert_eq!(runner.get_args().last(), Some(input.as_os_str()));
+        fs::remove_dir_all(&working_dir).unwrap();
+    }
+}
@@ -0,0 +1,118 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// System allocator counting the allocations of each thread, benchmarks register it as the
+/// `#[global_allocator]` to measure what parsing costs beyond time.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    // Growing a buffer is counted like a fresh allocation, as it may well be one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+// The thread may already be torn down, its allocations are not of interest then
+fn count() {
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+}
+
+/// Registers a `CountingAllocator` as the global allocator, which benchmarks and tests counting
+/// allocations need.
+#[macro_export]
+macro_rules! count_allocations_globally {
+    () => {
+        #[global_allocator]
+        static ALLOCATOR: $crate::CountingAllocator = $crate::CountingAllocator;
+    };
+}
+
+/// Runs `work` and counts the allocations it made on the current thread.
+///
+/// Always zero unless a `CountingAllocator` is the global allocator.
+pub fn count_allocations<T>(work: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = work();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+/// Parser compared by the `parse` benchmark of a day.
+pub type Parse<T> = fn(&str) -> T;
+
+/// Prints how many allocations each of the `parsers` makes on `input`, then hands it to `bench`
+/// to be timed.
+pub fn bench_parsers<T>(
+    input: &str,
+    parsers: &[(&str, Parse<T>)],
+    mut bench: impl FnMut(&str, Parse<T>),
+) {
+    for &(name, parse) in parsers {
+        let (_, allocations) = count_allocations(|| parse(input));
+        println!("parse/{}: {} allocations", name, allocations);
+        bench(name, parse);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    crate::count_allocations_globally!();
+
+    #[test]
+    fn test_count_allocations() {
+        let (_, allocations) = count_allocations(|| "borrowed".split(',').count());
+        assert_eq!(allocations, 0);
+
+        let (lines, allocations) =
+            count_allocations(|| "a\nb\nc".lines().map(String::from).collect::<Vec<String>>());
+        assert_eq!(lines.len(), 3);
+        assert_eq!(allocations, 4);
+    }
+
+    #[test]
+    fn test_growing_counts_as_allocation() {
+        let (_, allocations) = count_allocations(|| {
+            let mut items = Vec::with_capacity(1);
+            items.extend([1, 2, 3]);
+            items
+        });
+        assert_eq!(allocations, 2);
+    }
+
+    #[test]
+    fn test_bench_parsers() {
+        let mut benched = Vec::new();
+        let parsers: [(&str, Parse<usize>); 2] = [
+            ("borrowed", |input| input.lines().count()),
+            ("owned", |input| input.lines().map(String::from).count()),
+        ];
+        bench_parsers("a\nb", &parsers, |name, parse| {
+            benched.push((name.to_string(), parse("a\nb\nc")))
+        });
+        assert_eq!(
+            benched,
+            [("borrowed".to_string(), 3), ("owned".to_string(), 3)]
+        );
+    }
+}
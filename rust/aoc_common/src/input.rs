@@ -0,0 +1,220 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+
+/// Where a day reads its puzzle input from.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Input {
+    Stdin,
+    Path(String),
+    Inline(String),
+}
+
+impl Input {
+    /// Takes `--input PATH`, with `-` standing for stdin, or `--inline TEXT` out of the
+    /// arguments, leaving the others to the day.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Option<Input>, String> {
+        let mut input = None;
+        while let Some(index) = args
+            .iter()
+            .position(|arg| arg == "--input" || arg == "--inline")
+        {
+            if input.is_some() {
+                return Err("Give the input only once".to_string());
+            }
+            let value = args
+                .get(index + 1)
+                .cloned()
+                .ok_or_else(|| format!("Missing value for {}", args[index]))?;
+            input = Some(match (args[index].as_str(), value.as_str()) {
+                ("--input", "-") => Input::Stdin,
+                ("--input", _) => Input::Path(value),
+                _ => Input::Inline(value),
+            });
+            args.drain(index..index + 2);
+        }
+        Ok(input)
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            Input::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|error| format!("{}: {}", self, error))?;
+                Ok(text)
+            }
+            Input::Path(path) => {
+                fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))
+            }
+            Input::Inline(text) => Ok(text.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::Path(path) => write!(f, "{}", path),
+            Input::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+/// Reads the input given in the arguments, otherwise what is piped into the program, and
+/// otherwise the file at `default_path`. Returns where the input was read from with it.
+///
+/// Stdin is only read on its own when it is a pipe or a redirected file, so running a day
+/// without a terminal, as in CI or under a debugger, still reads the default file. Anything else
+/// is read with `--input -`. An empty pipe or file, as handed to a day by many scripts and
+/// runners, is ignored too; an empty input is solved only when asked for with `--input -`.
+pub fn read_input(args: &mut Vec<String>, default_path: &str) -> Result<(Input, String), String> {
+    let stdin = stdin_file_type().is_some_and(is_piped).then(io::stdin);
+    read_input_with(args, default_path, stdin)
+}
+
+// Like `read_input`, with the piped stdin if there is one
+fn read_input_with(
+    args: &mut Vec<String>,
+    default_path: &str,
+    stdin: Option<impl Read>,
+) -> Result<(Input, String), String> {
+    if let Some(input) = Input::take_from_args(args)? {
+        let text = input.read()?;
+        return Ok((input, text));
+    }
+
+    if let Some(mut stdin) = stdin {
+        let mut text = String::new();
+        stdin
+            .read_to_string(&mut text)
+            .map_err(|error| format!("{}: {}", Input::Stdin, error))?;
+        if !text.is_empty() {
+            return Ok((Input::Stdin, text));
+        }
+    }
+    let input = Input::Path(default_path.to_string());
+    let text = input.read()?;
+    Ok((input, text))
+}
+
+#[cfg(unix)]
+fn stdin_file_type() -> Option<fs::FileType> {
+    use std::os::fd::AsFd;
+
+    let stdin = io::stdin().as_fd().try_clone_to_owned().ok()?;
+    fs::File::from(stdin)
+        .metadata()
+        .ok()
+        .map(|metadata| metadata.file_type())
+}
+
+// Without a way to tell a pipe from a terminal, stdin has to be asked for
+#[cfg(not(unix))]
+fn stdin_file_type() -> Option<fs::FileType> {
+    None
+}
+
+#[cfg(unix)]
+fn is_piped(file_type: fs::FileType) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+
+    file_type.is_fifo() || file_type.is_file()
+}
+
+#[cfg(not(unix))]
+fn is_piped(file_type: fs::FileType) -> bool {
+    file_type.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_from_args() {
+        let mut given = args(&["day_4", "classify", "--input", "-", "--list"]);
+        assert_eq!(Input::take_from_args(&mut given), Ok(Some(Input::Stdin)));
+        assert_eq!(given, args(&["day_4", "classify", "--list"]));
+
+        let mut given = args(&["--inline", "2-4,6-8", "teams"]);
+        assert_eq!(
+            Input::take_from_args(&mut given),
+            Ok(Some(Input::Inline("2-4,6-8".to_string())))
+        );
+        assert_eq!(given, args(&["teams"]));
+
+        assert_eq!(Input::take_from_args(&mut args(&["teams"])), Ok(None));
+        assert_eq!(
+            Input::take_from_args(&mut args(&["--input"])),
+            Err("Missing value for --input".to_string())
+        );
+        assert_eq!(
+            Input::take_from_args(&mut args(&["--input", "a", "--inline", "b"])),
+            Err("Give the input only once".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let text = "1000\n2000\n";
+        assert_eq!(Input::Inline(text.to_string()).read(), Ok(text.to_string()));
+        assert_eq!(
+            Input::Path("./src/input.rs".to_string()).read(),
+            Ok(include_str!("input.rs").to_string())
+        );
+        assert!(Input::Path("./missing".to_string()).read().is_err());
+    }
+
+    #[test]
+    fn test_empty_pipe_reads_default_file() {
+        let default = include_str!("input.rs").to_string();
+        let read = |stdin: Option<&[u8]>, given: &[&str]| {
+            read_input_with(&mut args(given), "./src/input.rs", stdin)
+        };
+
+        assert_eq!(
+            read(Some(b""), &[]),
+            Ok((Input::Path("./src/input.rs".to_string()), default.clone()))
+        );
+        assert_eq!(read(None, &[]).map(|(_, text)| text), Ok(default));
+        assert_eq!(
+            read(Some(b"A Y\n"), &[]),
+            Ok((Input::Stdin, "A Y\n".to_string()))
+        );
+        assert_eq!(
+            read(Some(b"A Y\n"), &["--inline", ""]),
+            Ok((Input::Inline(String::new()), String::new()))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_empty_os_pipe_reads_default_file() {
+        let (reader, writer) = io::pipe().unwrap();
+        drop(writer);
+        assert_eq!(
+            read_input_with(&mut args(&[]), "./src/input.rs", Some(reader)),
+            Ok((
+                Input::Path("./src/input.rs".to_string()),
+                include_str!("input.rs").to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_is_piped() {
+        let file_type = |path: &str| fs::metadata(path).unwrap().file_type();
+        assert!(is_piped(file_type("./src/input.rs")));
+        assert!(!is_piped(file_type("./src")));
+        #[cfg(unix)]
+        assert!(!is_piped(file_type("/dev/null")));
+    }
+}
@@ -0,0 +1,10 @@
+pub use alloc::{bench_parsers, count_allocations, CountingAllocator, Parse};
+pub use input::{read_input, Input};
+pub use rng::Rng;
+pub use solution::Solution;
+
+pub mod alloc;
+pub mod input;
+pub mod parse;
+pub mod rng;
+pub mod solution;
@@ -0,0 +1,527 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! A parser is any `Fn(&str) -> Parsed<T>` returning the value and the input it left over.
+//! Values may borrow from the input, and failures point into it, so `parse` can report the
+//! line and column a grammar stopped matching at.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Value parsed from the start of the input and the rest of the input after it.
+pub type Parsed<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Failure at the start of `rest`, which is always a slice of the input being parsed.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Failure<'a> {
+    rest: &'a str,
+    reason: Reason,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Reason {
+    Expected(String),
+    Invalid(String),
+}
+
+impl<'a> Failure<'a> {
+    /// `what` was expected, the located error names what was found instead.
+    pub fn expected(rest: &'a str, what: impl Into<String>) -> Failure<'a> {
+        Failure {
+            rest,
+            reason: Reason::Expected(what.into()),
+        }
+    }
+
+    /// The text is well-formed but not a valid value, e.g. a number out of range.
+    pub fn invalid(rest: &'a str, message: impl Into<String>) -> Failure<'a> {
+        Failure {
+            rest,
+            reason: Reason::Invalid(message.into()),
+        }
+    }
+}
+
+/// Why the input could not be parsed, with line and column counted from 1.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Runs `parser` on the whole input, which it has to consume completely.
+pub fn parse<'a, T>(input: &'a str, parser: impl Fn(&'a str) -> Parsed<'a, T>) -> Result<T, Error> {
+    let (value, rest) = parser(input).map_err(|failure| locate(input, failure))?;
+    if !rest.is_empty() {
+        return Err(locate(input, Failure::expected(rest, "end of input")));
+    }
+    Ok(value)
+}
+
+// The failure is a slice of the input, so its address is its offset
+fn locate(input: &str, failure: Failure) -> Error {
+    let offset = (failure.rest.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|&offset| input.is_char_boundary(offset))
+        .unwrap_or(input.len());
+    let (before, after) = input.split_at(offset);
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    let message = match failure.reason {
+        Reason::Expected(what) => {
+            let found = match after.chars().next() {
+                None => "end of input".to_string(),
+                Some('\n') | Some('\r') => "end of line".to_string(),
+                Some(found) => format!("'{}'", found),
+            };
+            format!("expected {}, found {}", what, found)
+        }
+        Reason::Invalid(message) => message,
+    };
+    Error {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        message,
+    }
+}
+
+/// The literal text, e.g. a separator.
+pub fn tag<'a>(literal: &'static str) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(literal) {
+        Some(rest) => Ok((&input[..literal.len()], rest)),
+        None => Err(Failure::expected(input, format!("'{}'", literal))),
+    }
+}
+
+/// Unsigned decimal number, rejected at its first digit if it does not fit into `T`.
+pub fn number<'a, T: FromStr>() -> impl Fn(&'a str) -> Parsed<'a, T> {
+    move |input: &'a str| {
+        let (digits, rest) = input.split_at(
+            input
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(input.len()),
+        );
+        if digits.is_empty() {
+            return Err(Failure::expected(input, "a number"));
+        }
+        match digits.parse::<T>() {
+            Ok(number) => Ok((number, rest)),
+            Err(_) => Err(Failure::invalid(
+                input,
+                format!("number {} is too large", digits),
+            )),
+        }
+    }
+}
+
+/// Decimal number that may start with a minus sign, e.g. `-3`, rejected at its start if it
+/// does not fit into `T`.
+pub fn signed_number<'a, T: FromStr>() -> impl Fn(&'a str) -> Parsed<'a, T> {
+    move |input: &'a str| {
+        let digits = input.strip_prefix('-').unwrap_or(input);
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        if end == 0 {
+            return Err(Failure::expected(digits, "a number"));
+        }
+        let (text, rest) = input.split_at(input.len() - digits.len() + end);
+        match text.parse::<T>() {
+            Ok(number) => Ok((number, rest)),
+            Err(_) => Err(Failure::invalid(
+                input,
+                format!("number {} is out of range", text),
+            )),
+        }
+    }
+}
+
+/// Longest run of characters accepted by `predicate`, which may be empty.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input: &'a str| {
+        Ok(input.split_at(input.find(|c: char| !predicate(c)).unwrap_or(input.len())))
+    }
+}
+
+/// Field of exactly `width` characters within the line that `valid` accepts, `what` names
+/// the valid fields for the error.
+pub fn fixed<'a>(
+    width: usize,
+    what: &'static str,
+    valid: impl Fn(&str) -> bool,
+) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input: &'a str| {
+        let (field, rest) = input.split_at(
+            input
+                .char_indices()
+                .nth(width)
+                .map_or(input.len(), |(index, _)| index),
+        );
+        if field.chars().count() != width || field.contains(['\n', '\r']) || !valid(field) {
+            return Err(Failure::expected(input, what));
+        }
+        Ok((field, rest))
+    }
+}
+
+/// Both values around a literal separator, e.g. `2-4`.
+pub fn separated_pair<'a, A, B>(
+    first: impl Fn(&'a str) -> Parsed<'a, A>,
+    separator: &'static str,
+    second: impl Fn(&'a str) -> Parsed<'a, B>,
+) -> impl Fn(&'a str) -> Parsed<'a, (A, B)> {
+    let separator = tag(separator);
+    move |input: &'a str| {
+        let (one, rest) = first(input)?;
+        let (_, rest) = separator(rest)?;
+        let (two, rest) = second(rest)?;
+        Ok(((one, two), rest))
+    }
+}
+
+/// One or more values separated by a literal, e.g. `2-4,6-8,3-5`.
+pub fn separated_list<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+    separator: &'static str,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (value, mut rest) = parser(input)?;
+        let mut values = vec![value];
+        while let Some(next) = rest.strip_prefix(separator) {
+            let (value, after) = parser(next)?;
+            values.push(value);
+            rest = after;
+        }
+        Ok((values, rest))
+    }
+}
+
+pub fn map<'a, T, U>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+    f: impl Fn(T) -> U,
+) -> impl Fn(&'a str) -> Parsed<'a, U> {
+    move |input: &'a str| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Validates the parsed value, a rejection is located at the start of its text.
+pub fn try_map<'a, T, U, E: fmt::Display>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+    f: impl Fn(T) -> Result<U, E>,
+) -> impl Fn(&'a str) -> Parsed<'a, U> {
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(error) => Err(Failure::invalid(input, error.to_string())),
+        }
+    }
+}
+
+/// Folds every line of the input, each of which `parser` has to consume completely.
+///
+/// A rejection by `f` is located at the start of its line. Like `str::lines`, the last line
+/// may end with a newline and a carriage return before a newline is ignored.
+pub fn fold_lines<'a, T, U, E: fmt::Display>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+    init: impl Fn() -> U,
+    f: impl Fn(U, T) -> Result<U, E>,
+) -> impl Fn(&'a str) -> Parsed<'a, U> {
+    move |input: &'a str| {
+        let mut folded = init();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (line, next) = rest.split_at(rest.find('\n').map_or(rest.len(), |end| end + 1));
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let (value, unparsed) = parser(line)?;
+            if !unparsed.is_empty() {
+                return Err(Failure::expected(unparsed, "end of line"));
+            }
+            folded = f(folded, value).map_err(|error| Failure::invalid(line, error.to_string()))?;
+            rest = next;
+        }
+        Ok((folded, rest))
+    }
+}
+
+/// Every line of the input, each of which `parser` has to consume completely.
+///
+/// The values are collected in a single allocation, whatever the number of lines.
+pub fn lines<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input: &'a str| {
+        let capacity = input.lines().count();
+        let collect = fold_lines(
+            &parser,
+            || Vec::with_capacity(capacity),
+            |mut values, value| {
+                values.push(value);
+                Ok::<Vec<T>, Infallible>(values)
+            },
+        );
+        collect(input)
+    }
+}
+
+/// Blocks of lines separated by blank lines, each of which `parser` has to consume completely.
+///
+/// Any amount of blank lines separates two blocks, blank lines before the first block or
+/// after the last one are ignored. The values are collected in a single allocation, whatever
+/// the number of blocks.
+pub fn blocks<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut values = Vec::with_capacity(count_blocks(input));
+        let mut rest = skip_blank_lines(input);
+        while !rest.is_empty() {
+            let end = rest
+                .match_indices('\n')
+                .map(|(index, _)| index)
+                .find(|&index| starts_with_blank_line(&rest[index + 1..]))
+                .unwrap_or(rest.len());
+            let (value, unparsed) = parser(&rest[..end])?;
+            if !unparsed.is_empty() {
+                return Err(Failure::expected(unparsed, "a blank line"));
+            }
+            values.push(value);
+            rest = skip_blank_lines(&rest[end..]);
+        }
+        Ok((values, rest))
+    }
+}
+
+// Non-blank lines that follow a blank line or start the text
+fn count_blocks(text: &str) -> usize {
+    let mut after_blank = true;
+    text.lines()
+        .filter(|line| {
+            let starts_block = after_blank && !line.is_empty();
+            after_blank = line.is_empty();
+            starts_block
+        })
+        .count()
+}
+
+fn starts_with_blank_line(text: &str) -> bool {
+    text.is_empty() || text.starts_with('\n') || text.starts_with("\r\n")
+}
+
+fn skip_blank_lines(mut text: &str) -> &str {
+    while let Some(rest) = text
+        .strip_prefix('\n')
+        .or_else(|| text.strip_prefix("\r\n"))
+    {
+        text = rest;
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::count_allocations;
+
+    fn error(line: usize, column: usize, message: &str) -> Error {
+        Error {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    fn range<'a>() -> impl Fn(&'a str) -> Parsed<'a, (u8, u8)> {
+        separated_pair(number::<u8>(), "-", number::<u8>())
+    }
+
+    #[test]
+    fn test_number_and_separators() {
+        assert_eq!(parse("2-4", range()), Ok((2, 4)));
+        assert_eq!(
+            parse("2-x", range()),
+            Err(error(1, 3, "expected a number, found 'x'"))
+        );
+        assert_eq!(
+            parse("2,4", range()),
+            Err(error(1, 2, "expected '-', found ','"))
+        );
+        assert_eq!(
+            parse("2-400", range()),
+            Err(error(1, 3, "number 400 is too large"))
+        );
+        assert_eq!(
+            parse("2-4-", range()),
+            Err(error(1, 4, "expected end of input, found '-'"))
+        );
+    }
+
+    #[test]
+    fn test_lines_locate_errors() {
+        let grammar = lines(range());
+        assert_eq!(parse("1-2\n3-4\n", &grammar), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(parse("1-2\r\n3-4", &grammar), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(parse("", &grammar), Ok(vec![]));
+        assert_eq!(
+            parse("1-2\n3-4 \n", &grammar),
+            Err(error(2, 4, "expected end of line, found ' '"))
+        );
+        assert_eq!(
+            parse("1-2\n3-\n", &grammar),
+            Err(error(2, 3, "expected a number, found end of line"))
+        );
+    }
+
+    #[test]
+    fn test_fold_lines_locates_rejection_at_line() {
+        let sum = fold_lines(
+            number::<u8>(),
+            || 0u8,
+            |sum, value| sum.checked_add(value).ok_or("sum is too large"),
+        );
+        assert_eq!(parse("100\n100", &sum), Ok(200));
+        assert_eq!(
+            parse("100\n100\n100", &sum),
+            Err(error(3, 1, "sum is too large"))
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let grammar = blocks(lines(number::<u32>()));
+        assert_eq!(
+            parse("\n1\n2\n\n\n3\n\n", &grammar),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            parse("1\r\n\r\n2\r\n", &grammar),
+            Ok(vec![vec![1], vec![2]])
+        );
+        assert_eq!(
+            parse("1\n2\n\n3\nx", &grammar),
+            Err(error(5, 1, "expected a number, found 'x'"))
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks_allocate_once() {
+        for amount in [1, 10, 10_000] {
+            let input = "1\n2\n\n".repeat(amount);
+            let (numbers, allocations) =
+                count_allocations(|| parse(&input, lines(take_while(|c| c.is_ascii_digit()))));
+            assert_eq!((numbers.unwrap().len(), allocations), (3 * amount, 1));
+
+            let (sums, allocations) = count_allocations(|| {
+                parse(
+                    &input,
+                    blocks(fold_lines(
+                        number::<u32>(),
+                        || 0,
+                        |sum, n| Ok::<u32, Infallible>(sum + n),
+                    )),
+                )
+            });
+            assert_eq!((sums.unwrap().len(), allocations), (amount, 1));
+        }
+        assert_eq!(count_blocks("\n1\n2\n\n\n3\n\n"), 2);
+        assert_eq!(count_blocks("1\r\n\r\n2\r\n"), 2);
+        assert_eq!(count_blocks(""), 0);
+    }
+
+    #[test]
+    fn test_signed_number() {
+        assert_eq!(parse("-3", signed_number::<i64>()), Ok(-3));
+        assert_eq!(
+            parse(
+                "-3--1",
+                separated_pair(signed_number::<i8>(), "-", signed_number::<i8>())
+            ),
+            Ok((-3, -1))
+        );
+        assert_eq!(
+            parse("-x", signed_number::<i64>()),
+            Err(error(1, 2, "expected a number, found 'x'"))
+        );
+        assert_eq!(
+            parse("-129", signed_number::<i8>()),
+            Err(error(1, 1, "number -129 is out of range"))
+        );
+        assert_eq!(
+            parse("-1", signed_number::<u8>()),
+            Err(error(1, 1, "number -1 is out of range"))
+        );
+    }
+
+    #[test]
+    fn test_separated_list() {
+        let grammar = separated_list(range(), ",");
+        assert_eq!(parse("2-4", &grammar), Ok(vec![(2, 4)]));
+        assert_eq!(
+            parse("2-4,6-8,3-5", &grammar),
+            Ok(vec![(2, 4), (6, 8), (3, 5)])
+        );
+        assert_eq!(
+            parse("2-4,", &grammar),
+            Err(error(1, 5, "expected a number, found end of input"))
+        );
+        assert_eq!(
+            parse("", &grammar),
+            Err(error(1, 1, "expected a number, found end of input"))
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_fields() {
+        let shape = |shapes: &'static str| fixed(1, shapes, move |field| shapes.contains(field));
+        let grammar = lines(separated_pair(shape("ABC"), " ", shape("XYZ")));
+        assert_eq!(
+            parse("A X\nC Y", &grammar),
+            Ok(vec![("A", "X"), ("C", "Y")])
+        );
+        assert_eq!(
+            parse("A X\nC Q", &grammar),
+            Err(error(2, 3, "expected XYZ, found 'Q'"))
+        );
+        assert_eq!(
+            parse("A\n", &grammar),
+            Err(error(1, 2, "expected ' ', found end of line"))
+        );
+        assert_eq!(
+            parse("A ", &grammar),
+            Err(error(1, 3, "expected XYZ, found end of input"))
+        );
+    }
+
+    #[test]
+    fn test_try_map_and_take_while() {
+        let grammar = try_map(take_while(|c| c.is_ascii_alphabetic()), |items: &str| {
+            if items.len().is_multiple_of(2) {
+                Ok(items.split_at(items.len() / 2))
+            } else {
+                Err(format!("odd amount of items: {}", items.len()))
+            }
+        });
+        assert_eq!(parse("abCD", &grammar), Ok(("ab", "CD")));
+        assert_eq!(
+            parse("abC", &grammar),
+            Err(error(1, 1, "odd amount of items: 3"))
+        );
+        assert_eq!(
+            parse("ab1C", &grammar),
+            Err(error(1, 3, "expected end of input, found '1'"))
+        );
+        assert_eq!(parse("ab", map(tag("ab"), str::len)), Ok(2));
+    }
+}
@@ -0,0 +1,83 @@
+/// Small seeded xorshift64* generator, so generated inputs are reproducible on every platform.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Spread the seed with splitmix64, xorshift must never start at zero
+        let mut state = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        state ^= state >> 31;
+        Rng {
+            state: state.max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform number from `low` up to and including `high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        if low > high {
+            panic!("Lower bound {} must not exceed upper bound {}", low, high);
+        }
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.between(0, items.len() as u64 - 1) as usize]
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.between(0, index as u64) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn test_between_stays_in_bounds() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let number = rng.between(3, 9);
+            assert!((3..=9).contains(&number));
+        }
+        assert_eq!(rng.between(4, 4), 4);
+        rng.between(0, u64::MAX);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut rng = Rng::new(1);
+        let mut items = (0..20).collect::<Vec<u32>>();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
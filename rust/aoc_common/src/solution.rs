@@ -0,0 +1,23 @@
+use crate::Rng;
+
+/// Interface every day implements, so a runner can parse and solve it without knowing the day.
+///
+/// Parsing is separate from solving, so both can be timed on their own.
+pub trait Solution {
+    /// Parsed puzzle input, which may borrow from the text it was parsed from.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, String>;
+    fn part_one(input: &Self::Input<'_>) -> String;
+    fn part_two(input: &Self::Input<'_>) -> String;
+
+    /// Random valid input of `amount` items for differential testing, None without a generator.
+    fn generate(_amount: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    /// Answers of a naive reference implementation, None without an oracle.
+    fn oracle(_input: &str) -> Option<Result<[String; 2], String>> {
+        None
+    }
+}
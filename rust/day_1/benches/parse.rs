@@ -0,0 +1,47 @@
+use std::io::{BufRead, BufReader};
+
+use aoc_common::{bench_parsers, Rng, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_1::generate::generate;
+use day_1::Day1;
+
+aoc_common::count_allocations_globally!();
+
+// Copy of the original parser, a string per line and the items of an elf collected before
+// they are summed. Like the original it drops the last elf without a blank line after it.
+fn parse_baseline(input: &str) -> usize {
+    let mut elves: Vec<u64> = Vec::new();
+    let mut current_item: Vec<u64> = Vec::new();
+    for item in BufReader::new(input.as_bytes())
+        .lines()
+        .map_while(Result::ok)
+    {
+        if item.is_empty() {
+            elves.push(current_item.iter().sum());
+            current_item.clear();
+        } else {
+            current_item.push(item.parse::<u64>().unwrap())
+        }
+    }
+    elves.len()
+}
+
+fn parse_borrowed(input: &str) -> usize {
+    Day1::parse(input).unwrap().len()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = generate(10_000, &mut Rng::new(1));
+    let mut group = c.benchmark_group("parse");
+    bench_parsers(
+        &input,
+        &[("baseline", parse_baseline), ("borrowed", parse_borrowed)],
+        |name, parse| {
+            group.bench_function(name, |b| b.iter(|| parse(&input)));
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
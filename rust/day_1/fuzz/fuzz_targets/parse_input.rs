@@ -0,0 +1,13 @@
+#![no_main]
+
+use day_1::{most_calories, parse_input, top_three_calories};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(elves) = parse_input(input) {
+            most_calories(&elves);
+            top_three_calories(&elves);
+        }
+    }
+});
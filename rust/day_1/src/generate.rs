@@ -0,0 +1,28 @@
+use aoc_common::Rng;
+
+/// Calorie list of `amount` elves, each carrying between one and ten food items.
+pub fn generate(amount: usize, rng: &mut Rng) -> String {
+    let mut elves: Vec<String> = Vec::new();
+    for _ in 0..amount {
+        let items = (0..rng.between(1, 10))
+            .map(|_| rng.between(1000, 60000).to_string())
+            .collect::<Vec<String>>();
+        elves.push(items.join("\n") + "\n");
+    }
+    elves.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn test_generated_input_parses() {
+        let input = generate(50, &mut Rng::new(1));
+        let elves = parse_input(&input).unwrap();
+        assert_eq!(elves.len(), 50);
+        assert_eq!(input, generate(50, &mut Rng::new(1)));
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt;
+
+use aoc_common::parse::{self, blocks, fold_lines, map, number};
+use aoc_common::{Rng, Solution};
+
+pub mod generate;
+pub mod oracle;
+
+#[derive(PartialEq, Debug)]
+pub struct Elf {
+    pub num_of_calories: u64,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum ParseError {
+    TooManyCalories,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::TooManyCalories => {
+                write!(f, "Calories carried by an elf do not fit into 64 bits")
+            }
+        }
+    }
+}
+
+/// Whole input as a grammar: blocks of calories, one per elf, separated by blank lines. The
+/// last elf may or may not be followed by a blank line.
+pub fn parse_input(input: &str) -> Result<Vec<Elf>, parse::Error> {
+    let calories = fold_lines(
+        number::<u64>(),
+        || 0,
+        |sum: u64, calories| sum.checked_add(calories).ok_or(ParseError::TooManyCalories),
+    );
+    parse::parse(
+        input,
+        blocks(map(calories, |num_of_calories| Elf { num_of_calories })),
+    )
+}
+
+pub fn most_calories(elves: &[Elf]) -> u64 {
+    elves
+        .iter()
+        .map(|elf| elf.num_of_calories)
+        .max()
+        .unwrap_or(0)
+}
+
+// Three elves may carry more than fits into 64 bits
+pub fn top_three_calories(elves: &[Elf]) -> u128 {
+    let mut calories = elves
+        .iter()
+        .map(|elf| elf.num_of_calories)
+        .collect::<Vec<u64>>();
+    calories.sort_by(|a, b| b.cmp(a));
+    calories
+        .iter()
+        .take(3)
+        .map(|&calories| calories as u128)
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Vec<Elf>, String> {
+        parse_input(input).map_err(|error| error.to_string())
+    }
+
+    fn part_one(elves: &Vec<Elf>) -> String {
+        most_calories(elves).to_string()
+    }
+
+    fn part_two(elves: &Vec<Elf>) -> String {
+        top_three_calories(elves).to_string()
+    }
+
+    fn generate(amount: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate::generate(amount, rng))
+    }
+
+    fn oracle(input: &str) -> Option<Result<[String; 2], String>> {
+        Some(oracle::answers(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use aoc_common::{count_allocations, Rng};
+    use proptest::prelude::*;
+
+    aoc_common::count_allocations_globally!();
+
+    fn lines_of(elves: &[Vec<u64>]) -> Vec<String> {
+        elves
+            .iter()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<Vec<String>>>()
+            .join(&String::new())
+    }
+
+    #[test]
+    fn test_example() {
+        let elves =
+            parse_input("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000")
+                .unwrap();
+
+        assert_eq!(elves.len(), 5);
+        assert_eq!(most_calories(&elves), 24000);
+        assert_eq!(top_three_calories(&elves), 45000);
+    }
+
+    #[test]
+    fn test_parse_allocates_the_same_for_any_amount_of_lines() {
+        for amount in [10, 10_000] {
+            let input = generate::generate(amount, &mut Rng::new(1));
+            let (parsed, allocations) = count_allocations(|| Day1::parse(&input));
+            assert!(parsed.is_ok());
+            assert!(
+                allocations <= 1,
+                "{} allocations for {} elves",
+                allocations,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_input_locates_errors() {
+        assert_eq!(
+            parse_input("1000\n2000\n\n3000\n"),
+            Ok(vec![
+                Elf {
+                    num_of_calories: 3000
+                },
+                Elf {
+                    num_of_calories: 3000
+                }
+            ])
+        );
+        assert_eq!(
+            parse_input("1000\n\n20x0\n").unwrap_err().to_string(),
+            "line 3, column 3: expected end of line, found 'x'"
+        );
+        assert_eq!(
+            parse_input("1000\n-5\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a number, found '-'"
+        );
+        assert_eq!(parse_input("\n\n7\n\n\n").unwrap().len(), 1);
+        assert_eq!(
+            parse_input("1\n18446744073709551615\n").unwrap_err(),
+            parse::Error {
+                line: 2,
+                column: 1,
+                message: ParseError::TooManyCalories.to_string()
+            }
+        );
+    }
+
+    fn elves() -> impl Strategy<Value = Vec<Vec<u64>>> {
+        prop::collection::vec(prop::collection::vec(1..100_000u64, 1..10), 1..30)
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_input_keeps_every_elf(elves in elves(), trailing_blank_line in any::<bool>()) {
+            let mut input = lines_of(&elves).join("\n");
+            input.push_str(if trailing_blank_line { "\n\n" } else { "\n" });
+
+            let expected = elves
+                .iter()
+                .map(|items| Elf { num_of_calories: items.iter().sum() })
+                .collect::<Vec<Elf>>();
+            prop_assert_eq!(parse_input(&input), Ok(expected));
+        }
+
+        #[test]
+        fn test_top_three_is_at_least_max(elves in elves()) {
+            let elves = parse_input(&lines_of(&elves).join("\n")).unwrap();
+            let max = most_calories(&elves) as u128;
+            let top_three = top_three_calories(&elves);
+
+            prop_assert!(top_three >= max);
+            prop_assert!(top_three <= 3 * max);
+            if elves.len() <= 3 {
+                prop_assert_eq!(top_three, elves.iter().map(|elf| elf.num_of_calories as u128).sum::<u128>());
+            }
+        }
+    }
+}
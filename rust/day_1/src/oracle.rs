@@ -0,0 +1,64 @@
+/// Naive answers to both parts, for differential testing against the solution.
+///
+/// Works on the raw lines: any blank line ends a block and the three largest totals are
+/// found by repeatedly removing the largest one.
+pub fn answers(input: &str) -> Result<[String; 2], String> {
+    let mut totals: Vec<u128> = Vec::new();
+    let mut total = None;
+    for (index, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            totals.extend(total.take());
+            continue;
+        }
+        let calories = line
+            .parse::<u128>()
+            .map_err(|_| format!("line {}: '{}' is not a number", index + 1, line))?;
+        total = Some(total.unwrap_or(0) + calories);
+    }
+    totals.extend(total);
+
+    let mut top_three: Vec<u128> = Vec::new();
+    for _ in 0..3 {
+        let mut largest_index = None;
+        for index in 0..totals.len() {
+            if largest_index.is_none_or(|largest: usize| totals[index] > totals[largest]) {
+                largest_index = Some(index);
+            }
+        }
+        if let Some(index) = largest_index {
+            top_three.push(totals.remove(index));
+        }
+    }
+
+    Ok([
+        top_three.first().copied().unwrap_or(0).to_string(),
+        top_three.iter().sum::<u128>().to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        assert_eq!(
+            answers(input),
+            Ok(["24000".to_string(), "45000".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_runs_of_blank_lines() {
+        assert_eq!(
+            answers("\n1\n\n\n2\n3\n\n\n"),
+            Ok(["5".to_string(), "6".to_string()])
+        );
+        assert_eq!(
+            answers("1\n\n2x\n"),
+            Err("line 3: '2x' is not a number".to_string())
+        );
+    }
+}
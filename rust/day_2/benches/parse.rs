@@ -0,0 +1,44 @@
+use std::io::{BufRead, BufReader};
+
+use aoc_common::{bench_parsers, Rng, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_2::generate::generate;
+use day_2::{score_guide, score_round_part_one, score_round_part_two, Day2};
+
+aoc_common::count_allocations_globally!();
+
+// Copy of the original parser, a string per line split into a vector of both moves, which
+// are scored right away
+fn score_baseline(input: &str) -> (i64, i64) {
+    let mut score_part_one: i64 = 0;
+    let mut score_part_two: i64 = 0;
+    for c_line in BufReader::new(input.as_bytes())
+        .lines()
+        .map_while(Result::ok)
+    {
+        let split = c_line.split_whitespace().collect::<Vec<&str>>();
+        score_part_one += score_round_part_one(split[0], split[1]);
+        score_part_two += score_round_part_two(split[0], split[1]);
+    }
+    (score_part_one, score_part_two)
+}
+
+fn score_borrowed(input: &str) -> (i64, i64) {
+    score_guide(&Day2::parse(input).unwrap())
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = generate(10_000, &mut Rng::new(2));
+    let mut group = c.benchmark_group("parse");
+    bench_parsers(
+        &input,
+        &[("baseline", score_baseline), ("borrowed", score_borrowed)],
+        |name, parse| {
+            group.bench_function(name, |b| b.iter(|| parse(&input)));
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,12 @@
+#![no_main]
+
+use day_2::{parse_input, score_guide};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(rounds) = parse_input(input) {
+            score_guide(&rounds);
+        }
+    }
+});
@@ -0,0 +1,31 @@
+use aoc_common::Rng;
+
+const OPPONENTS_MOVES: [&str; 3] = ["A", "B", "C"];
+const MY_MOVES: [&str; 3] = ["X", "Y", "Z"];
+
+/// Strategy guide of `amount` random rounds.
+pub fn generate(amount: usize, rng: &mut Rng) -> String {
+    let mut guide = String::new();
+    for _ in 0..amount {
+        let round = format!(
+            "{} {}\n",
+            rng.choose(&OPPONENTS_MOVES),
+            rng.choose(&MY_MOVES)
+        );
+        guide.push_str(&round);
+    }
+    guide
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn test_generated_input_parses() {
+        let input = generate(50, &mut Rng::new(2));
+        assert_eq!(parse_input(&input).unwrap().len(), 50);
+    }
+}
@@ -0,0 +1,261 @@
+use aoc_common::parse::{self, fixed, lines, take_while, Failure, Parsed};
+use aoc_common::{Rng, Solution};
+
+pub mod generate;
+pub mod oracle;
+
+// A line of the input, both columns separated by whitespace. Like the `split_whitespace` the
+// guide was first read with, any amount of whitespace is accepted around the columns too.
+fn round<'a>() -> impl Fn(&'a str) -> Parsed<'a, (&'a str, &'a str)> {
+    let opponents_move = fixed(1, "A, B or C", |shape| ["A", "B", "C"].contains(&shape));
+    let my_move = fixed(1, "X, Y or Z", |shape| ["X", "Y", "Z"].contains(&shape));
+    let whitespace = take_while(char::is_whitespace);
+    move |line: &'a str| {
+        let (_, rest) = whitespace(line)?;
+        let (opponents_move, rest) = opponents_move(rest)?;
+        let (separator, rest) = whitespace(rest)?;
+        if separator.is_empty() {
+            return Err(Failure::expected(rest, "whitespace"));
+        }
+        let (my_move, rest) = my_move(rest)?;
+        let (_, rest) = whitespace(rest)?;
+        Ok(((opponents_move, my_move), rest))
+    }
+}
+
+/// Whole input as a grammar: one round per line, both columns separated by whitespace.
+pub fn parse_input(input: &str) -> Result<Vec<(&str, &str)>, parse::Error> {
+    parse::parse(input, lines(round()))
+}
+
+/// Total scores of the guide, with the second column read as the shape to play and as the
+/// outcome to reach.
+pub fn score_guide(rounds: &[(&str, &str)]) -> (i64, i64) {
+    rounds
+        .iter()
+        .fold((0, 0), |(part_one, part_two), (opponents_move, my_move)| {
+            (
+                part_one + score_round_part_one(opponents_move, my_move),
+                part_two + score_round_part_two(opponents_move, my_move),
+            )
+        })
+}
+
+// The second column is the shape I play
+pub fn score_round_part_one(opponents_move: &str, my_move: &str) -> i64 {
+    if (opponents_move == "A" && my_move == "X")
+        || (opponents_move == "B" && my_move == "Y")
+        || (opponents_move == "C" && my_move == "Z")
+    {
+        3 + get_shape_score(my_move)
+    } else if (opponents_move == "A" && my_move == "Z")
+        || (opponents_move == "B" && my_move == "X")
+        || (opponents_move == "C" && my_move == "Y")
+    {
+        get_shape_score(my_move)
+    } else if (opponents_move == "A" && my_move == "Y")
+        || (opponents_move == "B" && my_move == "Z")
+        || (opponents_move == "C" && my_move == "X")
+    {
+        6 + get_shape_score(my_move)
+    } else {
+        panic!("Unknown hand")
+    }
+}
+
+// The second column is the outcome I need
+pub fn score_round_part_two(opponents_move: &str, my_move: &str) -> i64 {
+    if my_move == "X" {
+        get_shape_score(select_losing_shape(opponents_move))
+    } else if my_move == "Y" {
+        3 + get_shape_score(select_drawing_shape(opponents_move))
+    } else if my_move == "Z" {
+        6 + get_shape_score(select_winning_shape(opponents_move))
+    } else {
+        panic!("Unknown move")
+    }
+}
+
+pub fn get_shape_score(shape: &str) -> i64 {
+    if shape == "X" {
+        1
+    } else if shape == "Y" {
+        2
+    } else if shape == "Z" {
+        3
+    } else {
+        panic!("Unknown shape: {}", shape)
+    }
+}
+
+pub fn select_winning_shape(shape: &str) -> &str {
+    if shape == "A" {
+        "Y"
+    } else if shape == "B" {
+        "Z"
+    } else if shape == "C" {
+        "X"
+    } else {
+        panic!("Opponent played unknown shape")
+    }
+}
+
+pub fn select_losing_shape(shape: &str) -> &str {
+    if shape == "A" {
+        "Z"
+    } else if shape == "B" {
+        "X"
+    } else if shape == "C" {
+        "Y"
+    } else {
+        panic!("Opponent played unknown shape")
+    }
+}
+
+pub fn select_drawing_shape(shape: &str) -> &str {
+    if shape == "A" {
+        "X"
+    } else if shape == "B" {
+        "Y"
+    } else if shape == "C" {
+        "Z"
+    } else {
+        panic!("Opponent played unknown shape")
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<(&'a str, &'a str)>;
+
+    fn parse(input: &str) -> Result<Vec<(&str, &str)>, String> {
+        parse_input(input).map_err(|error| error.to_string())
+    }
+
+    fn part_one(rounds: &Vec<(&str, &str)>) -> String {
+        score_guide(rounds).0.to_string()
+    }
+
+    fn part_two(rounds: &Vec<(&str, &str)>) -> String {
+        score_guide(rounds).1.to_string()
+    }
+
+    fn generate(amount: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate::generate(amount, rng))
+    }
+
+    fn oracle(input: &str) -> Option<Result<[String; 2], String>> {
+        Some(oracle::answers(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use aoc_common::{count_allocations, Rng};
+    use proptest::prelude::*;
+
+    aoc_common::count_allocations_globally!();
+
+    #[test]
+    fn test_example() {
+        let rounds = parse_input("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(score_guide(&rounds), (15, 12));
+    }
+
+    #[test]
+    fn test_parse_allocates_the_same_for_any_amount_of_lines() {
+        for amount in [10, 10_000] {
+            let input = generate::generate(amount, &mut Rng::new(2));
+            let (parsed, allocations) = count_allocations(|| Day2::parse(&input));
+            assert!(parsed.is_ok());
+            assert!(
+                allocations <= 1,
+                "{} allocations for {} rounds",
+                allocations,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_input_locates_errors() {
+        assert_eq!(parse_input("A Y\nB X\n"), Ok(vec![("A", "Y"), ("B", "X")]));
+        let error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            error("A Y\nD X"),
+            "line 2, column 1: expected A, B or C, found 'D'"
+        );
+        assert_eq!(
+            error("A Y Z"),
+            "line 1, column 5: expected end of line, found 'Z'"
+        );
+        assert_eq!(
+            error("A"),
+            "line 1, column 2: expected whitespace, found end of input"
+        );
+        assert_eq!(
+            error("AX"),
+            "line 1, column 2: expected whitespace, found 'X'"
+        );
+        assert_eq!(
+            error("A A"),
+            "line 1, column 3: expected X, Y or Z, found 'A'"
+        );
+    }
+
+    #[test]
+    fn test_parse_input_accepts_any_whitespace_around_columns() {
+        assert_eq!(
+            parse_input("A  Y\nB\tX \n  C Z\t\r\n"),
+            Ok(vec![("A", "Y"), ("B", "X"), ("C", "Z")])
+        );
+    }
+
+    fn round() -> impl Strategy<Value = (&'static str, &'static str)> {
+        (
+            prop::sample::select(vec!["A", "B", "C"]),
+            prop::sample::select(vec!["X", "Y", "Z"]),
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_scores_are_in_range((opponents_move, my_move) in round()) {
+            prop_assert!((1..=9).contains(&score_round_part_one(opponents_move, my_move)));
+            prop_assert!((1..=9).contains(&score_round_part_two(opponents_move, my_move)));
+        }
+
+        #[test]
+        fn test_parse_input_keeps_every_round(rounds in prop::collection::vec(round(), 0..50)) {
+            let input = rounds
+                .iter()
+                .map(|(opponents_move, my_move)| format!("{} {}\n", opponents_move, my_move))
+                .collect::<String>();
+            prop_assert_eq!(parse_input(&input), Ok(rounds));
+        }
+
+        #[test]
+        fn test_guide_score_is_sum_of_round_scores(rounds in prop::collection::vec(round(), 0..50)) {
+            let part_one = rounds.iter().map(|(o, m)| score_round_part_one(o, m)).sum::<i64>();
+            let part_two = rounds.iter().map(|(o, m)| score_round_part_two(o, m)).sum::<i64>();
+
+            prop_assert_eq!(score_guide(&rounds), (part_one, part_two));
+        }
+
+        #[test]
+        fn test_part_two_plays_the_selected_shape((opponents_move, my_move) in round()) {
+            let shape = match my_move {
+                "X" => select_losing_shape(opponents_move),
+                "Y" => select_drawing_shape(opponents_move),
+                _ => select_winning_shape(opponents_move),
+            };
+            prop_assert_eq!(
+                score_round_part_two(opponents_move, my_move),
+                score_round_part_one(opponents_move, shape)
+            );
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/// Naive answers to both parts, for differential testing against the solution.
+///
+/// Every one of the nine possible lines is scored by hand in a table.
+pub fn answers(input: &str) -> Result<[String; 2], String> {
+    const SCORES: [(&str, i64, i64); 9] = [
+        // Shape score plus outcome score, first for playing the second column as a shape,
+        // then for playing the shape that gives the second column as outcome
+        ("A X", 4, 3),
+        ("A Y", 8, 4),
+        ("A Z", 3, 8),
+        ("B X", 1, 1),
+        ("B Y", 5, 5),
+        ("B Z", 9, 9),
+        ("C X", 7, 2),
+        ("C Y", 2, 6),
+        ("C Z", 6, 7),
+    ];
+
+    let mut part_one = 0;
+    let mut part_two = 0;
+    for (index, line) in input.lines().enumerate() {
+        let line = line.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let (_, one, two) = SCORES
+            .iter()
+            .find(|(round, _, _)| *round == line)
+            .ok_or_else(|| format!("line {}: '{}' is not a round", index + 1, line))?;
+        part_one += one;
+        part_two += two;
+    }
+    Ok([part_one.to_string(), part_two.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            answers("A Y\nB X\nC Z\n"),
+            Ok(["15".to_string(), "12".to_string()])
+        );
+        assert_eq!(answers(" A  Y\t\n"), Ok(["8".to_string(), "4".to_string()]));
+        assert_eq!(
+            answers("A Y\nB W\n"),
+            Err("line 2: 'B W' is not a round".to_string())
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::io::{BufRead, BufReader};
+
+use aoc_common::{bench_parsers, Rng, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_3::generate::generate;
+use day_3::Day3;
+
+aoc_common::count_allocations_globally!();
+
+// Copy of the original parser, all lines read as strings first and then a string per item
+fn parse_baseline(input: &str) -> usize {
+    let all_lines = BufReader::new(input.as_bytes())
+        .lines()
+        .map_while(Result::ok)
+        .collect::<Vec<String>>();
+    all_lines
+        .iter()
+        .map(|all_items| parse_rucksack(all_items))
+        .collect::<Vec<(Vec<String>, Vec<String>)>>()
+        .len()
+}
+
+// Both compartments of a rucksack, a string per item
+fn parse_rucksack(all_items: &str) -> (Vec<String>, Vec<String>) {
+    let mut compartments = (Vec::new(), Vec::new());
+    let num_of_items = all_items.len();
+    for (pos, item) in all_items.chars().enumerate() {
+        if pos < num_of_items / 2 {
+            compartments.0.push(item.to_string())
+        } else {
+            compartments.1.push(item.to_string())
+        }
+    }
+    compartments
+}
+
+fn parse_borrowed(input: &str) -> usize {
+    Day3::parse(input).unwrap().len()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = generate(3_000, &mut Rng::new(3));
+    let mut group = c.benchmark_group("parse");
+    bench_parsers(
+        &input,
+        &[("baseline", parse_baseline), ("borrowed", parse_borrowed)],
+        |name, parse| {
+            group.bench_function(name, |b| b.iter(|| parse(&input)));
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
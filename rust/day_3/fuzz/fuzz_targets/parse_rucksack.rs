@@ -0,0 +1,26 @@
+#![no_main]
+
+use day_3::grouping::{find_optimal_groups, Objective};
+use day_3::{badge_priority_sum, oracle, parse_input, parse_rucksack_items, priority_sum};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for line in input.lines() {
+            if let Ok(rucksack) = parse_rucksack_items(line) {
+                priority_sum(&[rucksack]);
+            }
+        }
+
+        // Every input the grammar accepts is also solved by the naive oracle, to the same answers
+        if let Ok(rucksacks) = parse_input(input) {
+            let answers = [
+                priority_sum(&rucksacks).to_string(),
+                badge_priority_sum(&rucksacks).to_string(),
+            ];
+            assert_eq!(oracle::answers(input), Ok(answers));
+            let _ =
+                find_optimal_groups(&rucksacks[..rucksacks.len().min(9)], Objective::MaxPriority);
+        }
+    }
+});
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use aoc_common::Rng;
+
+const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksacks in groups of three, so `amount` is rounded up to whole groups.
+///
+/// Both compartments of every rucksack share exactly one item type, and the rucksacks of
+/// every group share exactly one badge.
+pub fn generate(amount: usize, rng: &mut Rng) -> String {
+    let item_types = ITEM_TYPES.chars().collect::<Vec<char>>();
+
+    let mut rucksacks = String::new();
+    for _ in 0..amount.div_ceil(3) {
+        let badge = *rng.choose(&item_types);
+        let mut in_every_rucksack: Option<HashSet<char>> = None;
+        for _ in 0..3 {
+            // Anything the previous rucksacks all hold would become a second badge
+            let allowed = item_types
+                .iter()
+                .copied()
+                .filter(|&item| item != badge)
+                .filter(|item| {
+                    in_every_rucksack
+                        .as_ref()
+                        .is_none_or(|common| !common.contains(item))
+                })
+                .collect::<Vec<char>>();
+            let rucksack = generate_rucksack(badge, allowed, rng);
+
+            let items = rucksack.chars().collect::<HashSet<char>>();
+            in_every_rucksack = Some(match in_every_rucksack {
+                None => items,
+                Some(common) => common.intersection(&items).copied().collect(),
+            });
+            rucksacks.push_str(&rucksack);
+            rucksacks.push('\n');
+        }
+    }
+    rucksacks
+}
+
+// The badge goes into the first compartment only, so it is never a duplicate itself
+fn generate_rucksack(badge: char, mut allowed: Vec<char>, rng: &mut Rng) -> String {
+    rng.shuffle(&mut allowed);
+    let duplicate = allowed[0];
+    let first_types = &allowed[1..1 + rng.between(1, 5) as usize];
+    let second_types = &allowed[6..6 + rng.between(1, 5) as usize];
+    let size = rng.between(2, 16) as usize;
+
+    let mut first = vec![badge, duplicate];
+    let mut second = vec![duplicate];
+    while first.len() < size {
+        first.push(*rng.choose(first_types));
+    }
+    while second.len() < size {
+        second.push(*rng.choose(second_types));
+    }
+    rng.shuffle(&mut first);
+    rng.shuffle(&mut second);
+
+    first.into_iter().chain(second).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{item_types, parse_input};
+
+    #[test]
+    fn test_generated_input_is_valid() {
+        let input = generate(100, &mut Rng::new(3));
+        let rucksacks = parse_input(&input).unwrap();
+        assert_eq!(rucksacks.len(), 102);
+
+        for rucksack in &rucksacks {
+            let shared =
+                item_types(rucksack.first_compartment) & item_types(rucksack.second_compartment);
+            assert_eq!(shared.count_ones(), 1);
+        }
+        for group in rucksacks.chunks_exact(3) {
+            let badges = group.iter().fold(u64::MAX, |badges, rucksack| {
+                badges
+                    & (item_types(rucksack.first_compartment)
+                        | item_types(rucksack.second_compartment))
+            });
+            assert_eq!(badges.count_ones(), 1);
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{item_type, item_types, RucksackItems};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Objective {
+    AnyValid,
+    MaxPriority,
+    MinPriority,
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(objective: &str) -> Result<Objective, String> {
+        match objective {
+            "any" => Ok(Objective::AnyValid),
+            "max" => Ok(Objective::MaxPriority),
+            "min" => Ok(Objective::MinPriority),
+            _ => Err(format!(
+                "Unknown objective '{}', expected any, max or min",
+                objective
+            )),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Partition {
+    pub members: Vec<[usize; 3]>,
+    pub badge_item_types: Vec<char>,
+    pub priority_sum: i32,
+    // False if the search gave up before every alternative was ruled out
+    pub proven_optimal: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum PartitionError {
+    NotDivisibleByThree(usize),
+    NoValidPartition,
+    SearchLimitReached(usize),
+}
+
+impl fmt::Display for PartitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartitionError::NotDivisibleByThree(amount) => write!(
+                f,
+                "{} rucksacks cannot be split into groups of three",
+                amount
+            ),
+            PartitionError::NoValidPartition => write!(
+                f,
+                "No partition exists in which every group shares exactly one badge"
+            ),
+            PartitionError::SearchLimitReached(limit) => {
+                write!(f, "No valid partition found within {} search steps", limit)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Triple {
+    members: [usize; 3],
+    priority: i32,
+}
+
+// Upper limit of explored search nodes before the best partition found so far is reported
+const SEARCH_LIMIT: usize = 20_000;
+
+struct Search {
+    objective: Objective,
+    triples: Vec<Triple>,
+    // Per rucksack, the valid triples it is part of, ordered so the most promising is tried first
+    containing: Vec<Vec<usize>>,
+    // Per rucksack, the number of valid triples whose members are all still unassigned
+    available: Vec<usize>,
+    // Per rucksack, the best priority any valid triple containing it can contribute
+    bounds: Vec<i32>,
+    assigned: Vec<bool>,
+    remaining_bound: i32,
+    current: Vec<usize>,
+    current_sum: i32,
+    best: Option<(i32, Vec<usize>)>,
+    explored: usize,
+    limit: usize,
+    // Set only when the limit stopped the search, not when it ended right at the limit
+    cut_off: bool,
+}
+
+/// Partitions the rucksacks, ignoring their order, into groups of three such that every
+/// group has exactly one common item type. Depending on the objective the first valid
+/// partition, or the one with the highest or lowest badge priority sum, is returned.
+pub fn find_optimal_groups(
+    rucksacks: &[RucksackItems],
+    objective: Objective,
+) -> Result<Partition, PartitionError> {
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(PartitionError::NotDivisibleByThree(rucksacks.len()));
+    }
+
+    let item_masks = rucksacks.iter().map(item_mask).collect::<Vec<u64>>();
+    let mut search = Search::new(&item_masks, objective, SEARCH_LIMIT);
+    if search.available.contains(&0) {
+        return Err(PartitionError::NoValidPartition);
+    }
+
+    search.run();
+    let proven_optimal = !search.cut_off;
+    let (_, chosen) = match search.best.take() {
+        Some(best) => best,
+        None if proven_optimal => return Err(PartitionError::NoValidPartition),
+        None => return Err(PartitionError::SearchLimitReached(SEARCH_LIMIT)),
+    };
+
+    let mut groups = chosen
+        .iter()
+        .map(|&triple| search.triples[triple])
+        .collect::<Vec<Triple>>();
+    groups.sort_by_key(|triple| triple.members);
+
+    Ok(Partition {
+        members: groups.iter().map(|triple| triple.members).collect(),
+        badge_item_types: groups
+            .iter()
+            .map(|triple| item_type(triple.priority))
+            .collect(),
+        priority_sum: groups.iter().map(|triple| triple.priority).sum(),
+        proven_optimal,
+    })
+}
+
+// Item types are mapped to their priority, so every rucksack fits into the 52 lowest bits
+fn item_mask(rucksack: &RucksackItems) -> u64 {
+    item_types(rucksack.first_compartment) | item_types(rucksack.second_compartment)
+}
+
+impl Search {
+    fn new(item_masks: &[u64], objective: Objective, limit: usize) -> Search {
+        let num_of_rucksacks = item_masks.len();
+        let mut triples: Vec<Triple> = Vec::new();
+        let mut containing: Vec<Vec<usize>> = vec![Vec::new(); num_of_rucksacks];
+        let mut bounds = vec![0; num_of_rucksacks];
+
+        for (i, mask_i) in item_masks.iter().enumerate() {
+            for (j, mask_j) in item_masks.iter().enumerate().skip(i + 1) {
+                let common_pair = mask_i & mask_j;
+                if common_pair == 0 {
+                    continue;
+                }
+                for (k, mask_k) in item_masks.iter().enumerate().skip(j + 1) {
+                    let common = common_pair & mask_k;
+                    if common.count_ones() != 1 {
+                        continue;
+                    }
+                    let priority = common.trailing_zeros() as i32 + 1;
+                    for member in [i, j, k] {
+                        containing[member].push(triples.len());
+                        bounds[member] = match (objective, bounds[member]) {
+                            (_, 0) => priority,
+                            (Objective::MinPriority, bound) => bound.min(priority),
+                            (_, bound) => bound.max(priority),
+                        };
+                    }
+                    triples.push(Triple {
+                        members: [i, j, k],
+                        priority,
+                    });
+                }
+            }
+        }
+
+        for candidates in containing.iter_mut() {
+            match objective {
+                Objective::MinPriority => {
+                    candidates.sort_by_key(|&triple| triples[triple].priority)
+                }
+                _ => candidates.sort_by_key(|&triple| -triples[triple].priority),
+            }
+        }
+
+        Search {
+            objective,
+            available: containing
+                .iter()
+                .map(|candidates| candidates.len())
+                .collect(),
+            triples,
+            containing,
+            remaining_bound: bounds.iter().sum(),
+            bounds,
+            assigned: vec![false; num_of_rucksacks],
+            current: Vec::new(),
+            current_sum: 0,
+            best: None,
+            explored: 0,
+            limit,
+            cut_off: false,
+        }
+    }
+
+    fn run(&mut self) {
+        if self.explored >= self.limit {
+            self.cut_off = true;
+            return;
+        }
+        self.explored += 1;
+
+        // Continue with the most constrained rucksack, giving up if any has no group left
+        let next = (0..self.assigned.len())
+            .filter(|&rucksack| !self.assigned[rucksack])
+            .min_by_key(|&rucksack| self.available[rucksack]);
+        let next = match next {
+            Some(next) => next,
+            None => {
+                if self.improves(self.current_sum) {
+                    self.best = Some((self.current_sum, self.current.clone()));
+                }
+                return;
+            }
+        };
+        if self.available[next] == 0 {
+            return;
+        }
+
+        for index in 0..self.containing[next].len() {
+            if self.objective == Objective::AnyValid && self.best.is_some() {
+                return;
+            }
+
+            let triple = self.triples[self.containing[next][index]];
+            if triple.members.iter().any(|&member| self.assigned[member]) {
+                continue;
+            }
+
+            let released: i32 = triple.members.iter().map(|&m| self.bounds[m]).sum();
+            // Each group contributes at most (or at least) the average bound of its members
+            let optimistic =
+                3 * (self.current_sum + triple.priority) + self.remaining_bound - released;
+            if !self.improves_bound(optimistic) {
+                continue;
+            }
+
+            for member in triple.members {
+                self.assign(member);
+            }
+            self.remaining_bound -= released;
+            self.current_sum += triple.priority;
+            self.current.push(self.containing[next][index]);
+
+            self.run();
+
+            self.current.pop();
+            self.current_sum -= triple.priority;
+            self.remaining_bound += released;
+            for member in triple.members.into_iter().rev() {
+                self.unassign(member);
+            }
+        }
+    }
+
+    fn assign(&mut self, rucksack: usize) {
+        for &triple in self.containing[rucksack].iter() {
+            let members = self.triples[triple].members;
+            if members.iter().all(|&member| !self.assigned[member]) {
+                for member in members {
+                    self.available[member] -= 1;
+                }
+            }
+        }
+        self.assigned[rucksack] = true;
+    }
+
+    fn unassign(&mut self, rucksack: usize) {
+        self.assigned[rucksack] = false;
+        for &triple in self.containing[rucksack].iter() {
+            let members = self.triples[triple].members;
+            if members.iter().all(|&member| !self.assigned[member]) {
+                for member in members {
+                    self.available[member] += 1;
+                }
+            }
+        }
+    }
+
+    fn improves(&self, priority_sum: i32) -> bool {
+        match (&self.best, self.objective) {
+            (None, _) => true,
+            (Some(_), Objective::AnyValid) => false,
+            (Some((best, _)), Objective::MaxPriority) => priority_sum > *best,
+            (Some((best, _)), Objective::MinPriority) => priority_sum < *best,
+        }
+    }
+
+    fn improves_bound(&self, tripled_priority_sum: i32) -> bool {
+        match (&self.best, self.objective) {
+            (None, _) | (_, Objective::AnyValid) => true,
+            (Some((best, _)), Objective::MaxPriority) => tripled_priority_sum > 3 * best,
+            (Some((best, _)), Objective::MinPriority) => tripled_priority_sum < 3 * best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{badge_priority_sum, parse_rucksack_items, priority_sum_of};
+    use proptest::prelude::*;
+
+    fn rucksacks<'a>(lines: &[&'a str]) -> Vec<RucksackItems<'a>> {
+        lines
+            .iter()
+            .map(|line| parse_rucksack_items(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_optimal_groups_ignores_input_order() {
+        let rucksacks = rucksacks(&[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "ttgJtRGJQctTZtZT",
+            "PmmdzqPrVvPwwTWBwg",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ]);
+
+        let partition = find_optimal_groups(&rucksacks, Objective::MaxPriority).unwrap();
+        assert_eq!(partition.members, vec![[0, 2, 4], [1, 3, 5]]);
+        assert_eq!(partition.badge_item_types, vec!['r', 'Z']);
+        assert_eq!(partition.priority_sum, 70);
+        assert!(partition.proven_optimal);
+    }
+
+    #[test]
+    fn test_search_ending_at_the_limit_is_not_cut_off() {
+        let item_masks = rucksacks(&[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "ttgJtRGJQctTZtZT",
+            "PmmdzqPrVvPwwTWBwg",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ])
+        .iter()
+        .map(item_mask)
+        .collect::<Vec<u64>>();
+        let search = |limit| {
+            let mut search = Search::new(&item_masks, Objective::MaxPriority, limit);
+            search.run();
+            search
+        };
+
+        let explored = search(SEARCH_LIMIT).explored;
+        let exact = search(explored);
+        assert!(!exact.cut_off);
+        assert_eq!(exact.best.map(|(sum, _)| sum), Some(70));
+        assert!(search(explored - 1).cut_off);
+    }
+
+    #[test]
+    fn test_find_optimal_groups_maximizes_and_minimizes_priority() {
+        let rucksacks = rucksacks(&["bd", "ac", "de", "ac", "ad", "cd"]);
+
+        let max = find_optimal_groups(&rucksacks, Objective::MaxPriority).unwrap();
+        assert_eq!(max.members, vec![[0, 2, 4], [1, 3, 5]]);
+        assert_eq!(max.priority_sum, 7);
+
+        let min = find_optimal_groups(&rucksacks, Objective::MinPriority).unwrap();
+        assert_eq!(min.members, vec![[0, 2, 5], [1, 3, 4]]);
+        assert_eq!(min.priority_sum, 5);
+    }
+
+    #[test]
+    fn test_find_optimal_groups_rejects_incomplete_groups() {
+        let rucksacks = rucksacks(&["aa", "aa", "aa", "aa"]);
+        assert_eq!(
+            find_optimal_groups(&rucksacks, Objective::AnyValid),
+            Err(PartitionError::NotDivisibleByThree(4))
+        );
+    }
+
+    #[test]
+    fn test_find_optimal_groups_reports_missing_partition() {
+        let rucksacks = rucksacks(&["ab", "ab", "ab", "cd", "ce", "fg"]);
+        assert_eq!(
+            find_optimal_groups(&rucksacks, Objective::AnyValid),
+            Err(PartitionError::NoValidPartition)
+        );
+    }
+
+    #[test]
+    fn test_parse_objective() {
+        assert_eq!("max".parse(), Ok(Objective::MaxPriority));
+        assert_eq!(
+            "best".parse::<Objective>(),
+            Err("Unknown objective 'best', expected any, max or min".to_string())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_triples_share_the_badges_of_badge_priority_sum(
+            lines in prop::collection::vec("([a-fA]{2}){1,4}", 3)
+        ) {
+            let rucksacks = rucksacks(&lines.iter().map(String::as_str).collect::<Vec<&str>>());
+            let item_masks = rucksacks.iter().map(item_mask).collect::<Vec<u64>>();
+            let common = item_masks.iter().fold(u64::MAX, |common, mask| common & mask);
+            prop_assert_eq!(priority_sum_of(common), badge_priority_sum(&rucksacks));
+
+            let search = Search::new(&item_masks, Objective::AnyValid, SEARCH_LIMIT);
+            match common.count_ones() {
+                1 => {
+                    prop_assert_eq!(search.triples.len(), 1);
+                    prop_assert_eq!(search.triples[0].priority, badge_priority_sum(&rucksacks));
+                }
+                _ => prop_assert_eq!(search.triples.len(), 0),
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt;
+
+use aoc_common::parse::{self, lines, take_while, try_map};
+use aoc_common::{Rng, Solution};
+
+pub mod generate;
+pub mod grouping;
+pub mod oracle;
+
+#[derive(PartialEq, Debug)]
+pub enum ParseError {
+    InvalidItem(char),
+    OddAmountOfItems(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidItem(item) => {
+                write!(f, "Item types must be ASCII letters: '{}'", item)
+            }
+            ParseError::OddAmountOfItems(amount) => write!(
+                f,
+                "Both compartments must hold the same amount of items, but there are {}",
+                amount
+            ),
+        }
+    }
+}
+
+/// Rucksack borrowing its items from the input, each item type is a single ASCII letter.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RucksackItems<'a> {
+    pub first_compartment: &'a [u8],
+    pub second_compartment: &'a [u8],
+}
+
+/// Splits the items into both compartments, which must hold the same amount of letters. Both
+/// compartments are slices of the line instead of copies.
+pub fn parse_rucksack_items(all_items: &str) -> Result<RucksackItems<'_>, ParseError> {
+    if let Some(item) = all_items.chars().find(|item| !item.is_ascii_alphabetic()) {
+        return Err(ParseError::InvalidItem(item));
+    }
+    if !all_items.len().is_multiple_of(2) {
+        return Err(ParseError::OddAmountOfItems(all_items.len()));
+    }
+
+    let (first_compartment, second_compartment) =
+        all_items.as_bytes().split_at(all_items.len() / 2);
+    Ok(RucksackItems {
+        first_compartment,
+        second_compartment,
+    })
+}
+
+/// Whole input as a grammar: one rucksack per line, its items split into both compartments.
+pub fn parse_input(input: &str) -> Result<Vec<RucksackItems<'_>>, parse::Error> {
+    let items = take_while(|item| item.is_ascii_alphabetic());
+    parse::parse(input, lines(try_map(items, parse_rucksack_items)))
+}
+
+// Item types are mapped to their priority, so a set of them fits into the 52 lowest bits
+pub(crate) fn item_types(items: &[u8]) -> u64 {
+    items.iter().fold(0, |types, &item| {
+        let priority = if item.is_ascii_lowercase() {
+            item - b'a' + 1
+        } else {
+            item - b'A' + 27
+        };
+        types | 1 << (priority - 1)
+    })
+}
+
+// Item type of a priority, the inverse of the mapping in `item_types`
+pub(crate) fn item_type(priority: i32) -> char {
+    match priority {
+        1..=26 => char::from(b'a' + priority as u8 - 1),
+        _ => char::from(b'A' + priority as u8 - 27),
+    }
+}
+
+pub(crate) fn priority_sum_of(types: u64) -> i32 {
+    (0..52)
+        .filter(|bit| types & 1 << bit != 0)
+        .map(|bit| bit + 1)
+        .sum()
+}
+
+/// Sum of the priorities of the item types in both compartments of a rucksack.
+pub fn priority_sum(rucksacks: &[RucksackItems]) -> i32 {
+    rucksacks
+        .iter()
+        .map(|rucksack| {
+            priority_sum_of(
+                item_types(rucksack.first_compartment) & item_types(rucksack.second_compartment),
+            )
+        })
+        .sum()
+}
+
+/// Sum of the priorities of the item types shared by consecutive groups of three rucksacks.
+/// Rucksacks left over after the last full group are ignored.
+pub fn badge_priority_sum(rucksacks: &[RucksackItems]) -> i32 {
+    rucksacks
+        .chunks_exact(3)
+        .map(|group| {
+            let badges = group
+                .iter()
+                .map(|rucksack| {
+                    item_types(rucksack.first_compartment) | item_types(rucksack.second_compartment)
+                })
+                .fold(u64::MAX, |badges, types| badges & types);
+            priority_sum_of(badges)
+        })
+        .sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<RucksackItems<'a>>;
+
+    fn parse(input: &str) -> Result<Vec<RucksackItems<'_>>, String> {
+        parse_input(input).map_err(|error| error.to_string())
+    }
+
+    fn part_one(rucksacks: &Vec<RucksackItems>) -> String {
+        priority_sum(rucksacks).to_string()
+    }
+
+    fn part_two(rucksacks: &Vec<RucksackItems>) -> String {
+        badge_priority_sum(rucksacks).to_string()
+    }
+
+    fn generate(amount: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate::generate(amount, rng))
+    }
+
+    fn oracle(input: &str) -> Option<Result<[String; 2], String>> {
+        Some(oracle::answers(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use aoc_common::{count_allocations, Rng};
+    use proptest::prelude::*;
+
+    aoc_common::count_allocations_globally!();
+
+    #[test]
+    fn test_parse_rucksack_items() {
+        let rucksack = parse_rucksack_items("aBcDEf").unwrap();
+        assert_eq!(rucksack.first_compartment, b"aBc");
+        assert_eq!(rucksack.second_compartment, b"DEf");
+        assert_eq!(
+            parse_rucksack_items("aBc"),
+            Err(ParseError::OddAmountOfItems(3))
+        );
+        assert_eq!(
+            parse_rucksack_items("ab1c"),
+            Err(ParseError::InvalidItem('1'))
+        );
+        assert_eq!(
+            parse_rucksack_items("aé"),
+            Err(ParseError::InvalidItem('é'))
+        );
+    }
+
+    #[test]
+    fn test_parse_allocates_the_same_for_any_amount_of_lines() {
+        for amount in [10, 10_000] {
+            let input = generate::generate(amount, &mut Rng::new(3));
+            let (parsed, allocations) = count_allocations(|| Day3::parse(&input));
+            assert!(parsed.is_ok());
+            assert!(
+                allocations <= 1,
+                "{} allocations for {} rucksacks",
+                allocations,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_input_locates_errors() {
+        let rucksacks = parse_input("abAB\ncdCD\n").unwrap();
+        assert_eq!(rucksacks[1].second_compartment, b"CD");
+        let error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            error("abAB\ncd1D"),
+            "line 2, column 3: expected end of line, found '1'"
+        );
+        assert_eq!(
+            error("abAB\nabc\n"),
+            format!("line 2, column 1: {}", ParseError::OddAmountOfItems(3))
+        );
+    }
+
+    #[test]
+    fn test_priority_sums() {
+        let rucksacks = parse_input(
+            "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+             jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+             PmmdzqPrVvPwwTWBwg\n\
+             wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+             ttgJtRGJQctTZtZT\n\
+             CrZsJsPPZsGzwwsLwLmpwMDw\n",
+        )
+        .unwrap();
+        assert_eq!(priority_sum(&rucksacks), 157);
+        assert_eq!(badge_priority_sum(&rucksacks), 70);
+        assert_eq!(badge_priority_sum(&rucksacks[..5]), 18);
+    }
+
+    #[test]
+    fn test_priority_sum_of() {
+        let types = item_types(b"pLPvts");
+        assert_eq!(priority_sum_of(types), 16 + 38 + 42 + 22 + 20 + 19);
+        assert_eq!(priority_sum_of(0), 0);
+    }
+
+    const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    fn shuffled_item_types() -> impl Strategy<Value = Vec<char>> {
+        Just(ITEM_TYPES.chars().collect::<Vec<char>>()).prop_shuffle()
+    }
+
+    // Both compartments share exactly the first item type
+    fn rucksack_with_duplicate() -> impl Strategy<Value = (char, String)> {
+        (shuffled_item_types(), 1..20usize).prop_map(|(items, size)| {
+            let first = items[1..size].iter().chain(&items[..1]);
+            let second = items[..1].iter().chain(&items[26..25 + size]);
+            (items[0], first.chain(second).collect())
+        })
+    }
+
+    // Three rucksacks sharing exactly the first item type
+    fn group_with_badge() -> impl Strategy<Value = (char, Vec<String>)> {
+        (shuffled_item_types(), [1..9usize, 1..9usize, 1..9usize]).prop_map(|(items, sizes)| {
+            let rucksacks = sizes
+                .iter()
+                .enumerate()
+                .map(|(index, size)| {
+                    let pool = &items[1 + 17 * index..1 + 17 * index + 2 * size - 1];
+                    pool.iter().chain(&items[..1]).collect::<String>()
+                })
+                .collect();
+            (items[0], rucksacks)
+        })
+    }
+
+    fn even_items() -> impl Strategy<Value = String> {
+        (0..12usize).prop_flat_map(|size| {
+            prop::string::string_regex(&format!("[a-zA-Z]{{{}}}", 2 * size)).unwrap()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_priorities_are_in_range(item in prop::sample::select(ITEM_TYPES.chars().collect::<Vec<char>>())) {
+            let priority = priority_sum_of(item_types(&[item as u8]));
+            prop_assert!((1..=52).contains(&priority));
+            prop_assert_eq!(ITEM_TYPES.chars().nth(priority as usize - 1), Some(item));
+            prop_assert_eq!(item_type(priority), item);
+        }
+
+        #[test]
+        fn test_parse_rucksack_items_splits_in_half(items in "[a-zA-Z]{0,20}") {
+            let items = format!("{}{}", items, items);
+            let rucksack = parse_rucksack_items(&items).unwrap();
+
+            prop_assert_eq!(rucksack.first_compartment.len(), rucksack.second_compartment.len());
+            let joined = [rucksack.first_compartment, rucksack.second_compartment].concat();
+            prop_assert_eq!(joined, items.as_bytes());
+        }
+
+        #[test]
+        fn test_priority_sums_match_oracle(lines in prop::collection::vec(even_items(), 0..12)) {
+            let input = lines.iter().map(|line| format!("{}\n", line)).collect::<String>();
+            let rucksacks = parse_input(&input).unwrap();
+            prop_assert_eq!(
+                [priority_sum(&rucksacks).to_string(), badge_priority_sum(&rucksacks).to_string()],
+                oracle::answers(&input).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_priority_sum_counts_shared_item((shared, items) in rucksack_with_duplicate()) {
+            let rucksack = parse_rucksack_items(&items).unwrap();
+            let types = item_types(rucksack.first_compartment) & item_types(rucksack.second_compartment);
+            prop_assert_eq!(types, item_types(&[shared as u8]));
+            prop_assert_eq!(priority_sum(&[rucksack]), priority_sum_of(types));
+        }
+
+        #[test]
+        fn test_badge_priority_sum_counts_shared_item((badge, lines) in group_with_badge()) {
+            let rucksacks = lines
+                .iter()
+                .map(|line| parse_rucksack_items(line).unwrap())
+                .collect::<Vec<RucksackItems>>();
+            let expected = priority_sum_of(item_types(&[badge as u8]));
+            prop_assert_eq!(badge_priority_sum(&rucksacks), expected);
+        }
+    }
+}
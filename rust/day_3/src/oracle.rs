@@ -0,0 +1,67 @@
+const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Naive answers to both parts, for differential testing against the solution.
+///
+/// Compares every item with every other one in nested loops and takes the priority from the
+/// position of the item in the alphabet.
+pub fn answers(input: &str) -> Result<[String; 2], String> {
+    let lines = input.lines().collect::<Vec<&str>>();
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.len() % 2 != 0 || !line.chars().all(|item| ITEM_TYPES.contains(item)) {
+            return Err(format!("line {}: '{}' is not a rucksack", index + 1, line));
+        }
+    }
+
+    let mut part_one = 0;
+    for line in &lines {
+        let items = line.chars().collect::<Vec<char>>();
+        let (first, second) = items.split_at(items.len() / 2);
+        for item_type in ITEM_TYPES.chars() {
+            let mut in_first = false;
+            let mut in_second = false;
+            for &item in first {
+                in_first |= item == item_type;
+            }
+            for &item in second {
+                in_second |= item == item_type;
+            }
+            if in_first && in_second {
+                part_one += priority(item_type);
+            }
+        }
+    }
+
+    let mut part_two = 0;
+    for group in lines.chunks_exact(3) {
+        for item_type in ITEM_TYPES.chars() {
+            if group.iter().all(|rucksack| rucksack.contains(item_type)) {
+                part_two += priority(item_type);
+            }
+        }
+    }
+
+    Ok([part_one.to_string(), part_two.to_string()])
+}
+
+fn priority(item_type: char) -> usize {
+    ITEM_TYPES.find(item_type).unwrap() + 1
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input =
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n\
+                     wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+        assert_eq!(answers(input), Ok(["157".to_string(), "70".to_string()]));
+        assert_eq!(
+            answers("abab\nab1b\n"),
+            Err("line 2: 'ab1b' is not a rucksack".to_string())
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use aoc_common::Rng;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_4::{find_overlapping_pairs, find_overlapping_pairs_pairwise, ElfId, Range};
+
+// Spreads short ranges over a section space growing with the input, as a large camp would
+fn generate_elves(amount: usize) -> Vec<(ElfId, Range)> {
+    let mut rng = Rng::new(4);
+    (0..amount)
+        .map(|index| {
+            let start = rng.between(0, 10 * amount as u64 - 1);
+            let end = start + rng.between(0, 99);
+            let id = ElfId {
+                line: index / 2,
+                elf: index % 2,
+            };
+            (id, Range::new(start, end))
+        })
+        .collect()
+}
+
+fn bench_cross_overlap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cross_overlap");
+    for amount in [1_000, 10_000, 100_000] {
+        let elves = generate_elves(amount);
+        group.bench_with_input(BenchmarkId::new("sweep", amount), &elves, |b, elves| {
+            b.iter(|| find_overlapping_pairs(elves))
+        });
+        if amount <= 10_000 {
+            group.bench_with_input(BenchmarkId::new("pairwise", amount), &elves, |b, elves| {
+                b.iter(|| find_overlapping_pairs_pairwise(elves))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cross_overlap);
+criterion_main!(benches);
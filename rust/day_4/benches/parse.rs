@@ -0,0 +1,51 @@
+use std::io::{BufRead, BufReader};
+
+use aoc_common::{bench_parsers, Rng, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_4::generate::generate;
+use day_4::{Assignment, Day4, Range};
+
+aoc_common::count_allocations_globally!();
+
+// Copy of the original parser, a string per line and a vector for the parts of every split
+fn parse_baseline(input: &str) -> Vec<Assignment> {
+    BufReader::new(input.as_bytes())
+        .lines()
+        .map(|line| {
+            let line = line.unwrap();
+            let ranges = line.split(',').collect::<Vec<&str>>();
+            let range_one = ranges[0].split('-').collect::<Vec<&str>>();
+            let range_two = ranges[1].split('-').collect::<Vec<&str>>();
+            Assignment {
+                assignment_one: Range::new(
+                    range_one[0].parse::<u64>().unwrap(),
+                    range_one[1].parse::<u64>().unwrap(),
+                ),
+                assignment_two: Range::new(
+                    range_two[0].parse::<u64>().unwrap(),
+                    range_two[1].parse::<u64>().unwrap(),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn parse_borrowed(input: &str) -> Vec<Assignment> {
+    Day4::parse(input).unwrap()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = generate(10_000, &mut Rng::new(4));
+    let mut group = c.benchmark_group("parse");
+    bench_parsers(
+        &input,
+        &[("baseline", parse_baseline), ("borrowed", parse_borrowed)],
+        |name, parse| {
+            group.bench_function(name, |b| b.iter(|| parse(&input)));
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_4::{rebalance_all, Assignment, Range};
+
+// Every elf overlaps the next, so all of them form a single group to split
+fn generate_chain(lines: u64) -> Vec<Assignment> {
+    (0..lines)
+        .map(|line| Assignment {
+            assignment_one: Range::new(2 * line, 2 * line + 2),
+            assignment_two: Range::new(2 * line + 1, 2 * line + 3),
+        })
+        .collect()
+}
+
+fn bench_rebalance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rebalance");
+    for lines in [1_000, 10_000, 100_000] {
+        let assignments = generate_chain(lines);
+        group.bench_with_input(
+            BenchmarkId::new("chain", lines),
+            &assignments,
+            |b, assignments| b.iter(|| rebalance_all(assignments)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rebalance);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+#![no_main]
+
+use day_4::{
+    assignment_have_overlapping_ranges, classify, is_either_fully_contained, parse_assignment,
+    rebalance,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for line in input.lines() {
+            if let Ok(assignment) = parse_assignment(line) {
+                is_either_fully_contained(&assignment);
+                assignment_have_overlapping_ranges(&assignment);
+                classify(&assignment);
+                rebalance(&assignment);
+            }
+        }
+    }
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use day_4::{
+    assignment_have_overlapping_ranges, is_either_fully_contained, parse_assignment_with, Syntax,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The first byte picks the syntax so every combination is covered
+    if let Some((&options, rest)) = data.split_first() {
+        let syntax = Syntax {
+            extended: options & 1 != 0,
+            normalize_reversed: options & 2 != 0,
+        };
+        if let Ok(input) = std::str::from_utf8(rest) {
+            for line in input.lines() {
+                if let Ok(assignment) = parse_assignment_with(line, &syntax) {
+                    is_either_fully_contained(&assignment);
+                    assignment_have_overlapping_ranges(&assignment);
+                }
+            }
+        }
+    }
+});
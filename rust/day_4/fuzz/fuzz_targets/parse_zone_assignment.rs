@@ -0,0 +1,19 @@
+#![no_main]
+
+use day_4::{
+    intersection_volume, is_either_zone_fully_contained, parse_zone_assignment,
+    zone_assignment_have_overlapping_zones,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for line in input.lines() {
+            if let Ok(assignment) = parse_zone_assignment(line) {
+                is_either_zone_fully_contained(&assignment);
+                zone_assignment_have_overlapping_zones(&assignment);
+                intersection_volume(&assignment.zone_one, &assignment.zone_two);
+            }
+        }
+    }
+});
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::Assignment;
+
+/// How the two ranges of an assignment relate to each other.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum OverlapClass {
+    /// A gap of at least one section lies between the ranges.
+    Disjoint,
+    /// The ranges do not overlap, but no section lies between them.
+    Adjacent,
+    /// Range one overlaps the start of range two.
+    PartialLeft,
+    /// Range one overlaps the end of range two.
+    PartialRight,
+    OneContainsTwo,
+    TwoContainsOne,
+    Identical,
+}
+
+impl fmt::Display for OverlapClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OverlapClass::Disjoint => "disjoint",
+            OverlapClass::Adjacent => "adjacent",
+            OverlapClass::PartialLeft => "partial-left",
+            OverlapClass::PartialRight => "partial-right",
+            OverlapClass::OneContainsTwo => "one-contains-two",
+            OverlapClass::TwoContainsOne => "two-contains-one",
+            OverlapClass::Identical => "identical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub fn classify(assignment: &Assignment) -> OverlapClass {
+    let one = &assignment.assignment_one;
+    let two = &assignment.assignment_two;
+
+    if one == two {
+        OverlapClass::Identical
+    } else if one.contains_range(two) {
+        OverlapClass::OneContainsTwo
+    } else if two.contains_range(one) {
+        OverlapClass::TwoContainsOne
+    } else if one.overlaps(two) && one.start < two.start {
+        OverlapClass::PartialLeft
+    } else if one.overlaps(two) {
+        OverlapClass::PartialRight
+    } else if one.is_adjacent(two) {
+        OverlapClass::Adjacent
+    } else {
+        OverlapClass::Disjoint
+    }
+}
+
+/// Number of sections both elves have to clean.
+pub fn overlap_length(assignment: &Assignment) -> u128 {
+    assignment
+        .assignment_one
+        .intersection(&assignment.assignment_two)
+        .map_or(0, |common| common.len())
+}
+
+/// Indices of the assignments belonging to each class, for every class that occurs.
+pub fn classify_all(assignments: &[Assignment]) -> BTreeMap<OverlapClass, Vec<usize>> {
+    let mut classes: BTreeMap<OverlapClass, Vec<usize>> = BTreeMap::new();
+    for (index, assignment) in assignments.iter().enumerate() {
+        classes.entry(classify(assignment)).or_default().push(index);
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{
+        assignment_have_overlapping_ranges, is_either_fully_contained, parse_assignment, Range,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_classify_every_class() {
+        let classes = [
+            "2-4,6-8", "2-3,4-5", "5-7,7-9", "6-8,3-6", "2-8,3-7", "4-6,3-8", "6-6,6-6",
+        ]
+        .iter()
+        .map(|line| classify(&parse_assignment(line).unwrap()))
+        .collect::<Vec<OverlapClass>>();
+
+        assert_eq!(
+            classes,
+            vec![
+                OverlapClass::Disjoint,
+                OverlapClass::Adjacent,
+                OverlapClass::PartialLeft,
+                OverlapClass::PartialRight,
+                OverlapClass::OneContainsTwo,
+                OverlapClass::TwoContainsOne,
+                OverlapClass::Identical,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_all_groups_lines() {
+        let assignments = ["2-4,6-8", "5-7,7-9", "2-4,6-8", "2-8,3-7"]
+            .iter()
+            .map(|line| parse_assignment(line).unwrap())
+            .collect::<Vec<Assignment>>();
+        let classes = classify_all(&assignments);
+
+        assert_eq!(classes[&OverlapClass::Disjoint], vec![0, 2]);
+        assert_eq!(classes[&OverlapClass::PartialLeft], vec![1]);
+        assert_eq!(classes[&OverlapClass::OneContainsTwo], vec![3]);
+        assert!(!classes.contains_key(&OverlapClass::Identical));
+        assert_eq!(overlap_length(&assignments[3]), 5);
+    }
+
+    proptest! {
+        #[test]
+        fn test_classify_agrees_with_predicates(a in 0..20u64, b in 0..20u64, c in 0..20u64, d in 0..20u64) {
+            let assignment = Assignment {
+                assignment_one: Range::new(a.min(b), a.max(b)),
+                assignment_two: Range::new(c.min(d), c.max(d)),
+            };
+            let class = classify(&assignment);
+
+            let contained = matches!(
+                class,
+                OverlapClass::OneContainsTwo | OverlapClass::TwoContainsOne | OverlapClass::Identical
+            );
+            prop_assert_eq!(contained, is_either_fully_contained(&assignment));
+
+            let overlapping = !matches!(class, OverlapClass::Disjoint | OverlapClass::Adjacent);
+            prop_assert_eq!(overlapping, assignment_have_overlapping_ranges(&assignment));
+            prop_assert_eq!(overlapping, overlap_length(&assignment) > 0);
+        }
+    }
+}
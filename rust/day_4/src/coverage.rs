@@ -0,0 +1,194 @@
+use crate::range::{Range, SectionId};
+
+/// Consecutive sections that are all assigned to the same number of elves.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Segment<T = u64> {
+    pub range: Range<T>,
+    pub elves: usize,
+}
+
+/// Number of elves assigned to each section, from the lowest to the highest assigned section.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Coverage<T = u64> {
+    segments: Vec<Segment<T>>,
+}
+
+impl<T: SectionId> Coverage<T> {
+    /// Sweeps over the start and end of every range once, instead of comparing them pairwise.
+    pub fn sweep<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Coverage<T> {
+        let mut events: Vec<(T, isize)> = Vec::new();
+        let mut highest_end: Option<T> = None;
+        for range in ranges {
+            highest_end = highest_end.max(Some(range.end));
+            events.push((range.start, 1));
+            // A range ending at the highest ID never stops counting
+            if let Some(after_end) = range.end.successor() {
+                events.push((after_end, -1));
+            }
+        }
+        events.sort();
+
+        let mut segments: Vec<Segment<T>> = Vec::new();
+        let mut elves: isize = 0;
+        let mut index = 0;
+        while index < events.len() {
+            let position = events[index].0;
+            while index < events.len() && events[index].0 == position {
+                elves += events[index].1;
+                index += 1;
+            }
+
+            let end = match events.get(index) {
+                Some(&(next, _)) => next.predecessor().unwrap(),
+                None if elves > 0 => highest_end.unwrap(),
+                None => break,
+            };
+            let segment = Segment {
+                range: Range {
+                    start: position,
+                    end,
+                },
+                elves: elves as usize,
+            };
+            match segments.last_mut() {
+                Some(last) if last.elves == segment.elves => last.range.end = end,
+                _ => segments.push(segment),
+            }
+        }
+
+        Coverage { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment<T>] {
+        &self.segments
+    }
+
+    pub fn elves_at(&self, section: T) -> usize {
+        let index = self.segments.partition_point(|s| s.range.end < section);
+        match self.segments.get(index) {
+            Some(segment) if segment.range.contains(section) => segment.elves,
+            _ => 0,
+        }
+    }
+
+    /// Highest number of elves assigned to the same section at once.
+    pub fn max_overlap(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|segment| segment.elves)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Segments ordered from the most to the least assigned, ties by ascending section.
+    pub fn most_assigned(&self, amount: usize) -> Vec<Segment<T>> {
+        let mut segments = self.segments.clone();
+        segments.sort_by(|a, b| {
+            b.elves
+                .cmp(&a.elves)
+                .then(a.range.start.cmp(&b.range.start))
+        });
+        segments.truncate(amount);
+        segments
+    }
+
+    /// Sections between the lowest and highest assigned section that no elf cleans.
+    pub fn unassigned(&self) -> Vec<Range<T>> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves == 0)
+            .map(|segment| segment.range)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sweep_counts_elves_per_section() {
+        let coverage = Coverage::sweep([
+            Range::new(2, 4),
+            Range::new(6, 8),
+            Range::new(3, 7),
+            Range::new(11, 12),
+        ]);
+        assert_eq!(
+            coverage.segments(),
+            &[
+                Segment {
+                    range: Range::new(2, 2),
+                    elves: 1
+                },
+                Segment {
+                    range: Range::new(3, 4),
+                    elves: 2
+                },
+                Segment {
+                    range: Range::new(5, 5),
+                    elves: 1
+                },
+                Segment {
+                    range: Range::new(6, 7),
+                    elves: 2
+                },
+                Segment {
+                    range: Range::new(8, 8),
+                    elves: 1
+                },
+                Segment {
+                    range: Range::new(9, 10),
+                    elves: 0
+                },
+                Segment {
+                    range: Range::new(11, 12),
+                    elves: 1
+                },
+            ]
+        );
+        assert_eq!(coverage.max_overlap(), 2);
+        assert_eq!(coverage.unassigned(), vec![Range::new(9, 10)]);
+        assert_eq!(
+            coverage.most_assigned(1),
+            vec![Segment {
+                range: Range::new(3, 4),
+                elves: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_sweep_up_to_highest_section() {
+        let coverage = Coverage::sweep([Range::new(250u8, 255), Range::new(255, 255)]);
+        assert_eq!(coverage.elves_at(255), 2);
+        assert_eq!(coverage.elves_at(249), 0);
+        assert_eq!(coverage.segments().last().unwrap().range.end, 255);
+    }
+
+    proptest! {
+        #[test]
+        fn test_sweep_matches_counting_every_section(
+            bounds in prop::collection::vec((0..30u8, 0..30u8), 0..12),
+        ) {
+            let ranges = bounds
+                .iter()
+                .map(|&(a, b)| Range::new(a.min(b), a.max(b)))
+                .collect::<Vec<Range<u8>>>();
+            let coverage = Coverage::sweep(ranges.clone());
+
+            let mut max_overlap = 0;
+            for section in 0..32u8 {
+                let elves = ranges.iter().filter(|range| range.contains(section)).count();
+                prop_assert_eq!(coverage.elves_at(section), elves);
+                max_overlap = max_overlap.max(elves);
+            }
+            prop_assert_eq!(coverage.max_overlap(), max_overlap);
+
+            let merged = coverage.segments().windows(2).any(|pair| pair[0].elves == pair[1].elves);
+            prop_assert!(!merged);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::range::{Range, SectionId};
+
+/// Identifies an elf by the input line of its assignment and its position within that line.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct ElfId {
+    pub line: usize,
+    pub elf: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct OverlappingPair {
+    pub first: ElfId,
+    pub second: ElfId,
+    pub fully_contained: bool,
+}
+
+impl OverlappingPair {
+    fn new<T: SectionId>(one: (ElfId, &Range<T>), two: (ElfId, &Range<T>)) -> OverlappingPair {
+        let (first, second) = if one.0 <= two.0 {
+            (one, two)
+        } else {
+            (two, one)
+        };
+        OverlappingPair {
+            first: first.0,
+            second: second.0,
+            fully_contained: first.1.contains_range(second.1) || second.1.contains_range(first.1),
+        }
+    }
+}
+
+/// Lists every pair of elves with overlapping ranges in O(n log n + k) for k pairs.
+///
+/// Elves are visited by ascending start while a heap keeps those still active ordered by
+/// end. Once every elf ending before the current start is dropped, all remaining ones
+/// overlap the current elf, so no comparison is wasted on disjoint ranges.
+pub fn find_overlapping_pairs<T: SectionId>(elves: &[(ElfId, Range<T>)]) -> Vec<OverlappingPair> {
+    let mut order = (0..elves.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&index| elves[index].1.start);
+
+    let mut pairs = Vec::new();
+    let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+    for index in order {
+        let (id, range) = &elves[index];
+        while let Some(Reverse((end, _))) = active.peek() {
+            if *end >= range.start {
+                break;
+            }
+            active.pop();
+        }
+
+        for Reverse((_, other)) in active.iter() {
+            let (other_id, other_range) = &elves[*other];
+            pairs.push(OverlappingPair::new((*id, range), (*other_id, other_range)));
+        }
+        active.push(Reverse((range.end, index)));
+    }
+    pairs
+}
+
+/// Compares every elf with every other one, used as a reference for the sweep.
+pub fn find_overlapping_pairs_pairwise<T: SectionId>(
+    elves: &[(ElfId, Range<T>)],
+) -> Vec<OverlappingPair> {
+    let mut pairs = Vec::new();
+    for (index, (id, range)) in elves.iter().enumerate() {
+        for (other_id, other_range) in elves.iter().skip(index + 1) {
+            if range.overlaps(other_range) {
+                pairs.push(OverlappingPair::new((*id, range), (*other_id, other_range)));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn elves(ranges: &[(u64, u64)]) -> Vec<(ElfId, Range)> {
+        ranges
+            .iter()
+            .enumerate()
+            .map(|(index, &(start, end))| {
+                let id = ElfId {
+                    line: index / 2,
+                    elf: index % 2,
+                };
+                (id, Range::new(start, end))
+            })
+            .collect()
+    }
+
+    fn sorted(mut pairs: Vec<OverlappingPair>) -> Vec<OverlappingPair> {
+        pairs.sort_by_key(|pair| (pair.first, pair.second));
+        pairs
+    }
+
+    #[test]
+    fn test_find_overlapping_pairs_across_lines() {
+        let elves = elves(&[(2, 4), (6, 8), (3, 3), (9, 9), (8, 10), (1, 1)]);
+        let id = |line, elf| ElfId { line, elf };
+
+        assert_eq!(
+            sorted(find_overlapping_pairs(&elves)),
+            vec![
+                OverlappingPair {
+                    first: id(0, 0),
+                    second: id(1, 0),
+                    fully_contained: true,
+                },
+                OverlappingPair {
+                    first: id(0, 1),
+                    second: id(2, 0),
+                    fully_contained: false,
+                },
+                OverlappingPair {
+                    first: id(1, 1),
+                    second: id(2, 0),
+                    fully_contained: true,
+                },
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_sweep_matches_pairwise_comparison(
+            bounds in prop::collection::vec((0..50u64, 0..50u64), 0..40),
+        ) {
+            let ranges = bounds
+                .iter()
+                .map(|&(a, b)| (a.min(b), a.max(b)))
+                .collect::<Vec<(u64, u64)>>();
+            let elves = elves(&ranges);
+
+            prop_assert_eq!(
+                sorted(find_overlapping_pairs(&elves)),
+                sorted(find_overlapping_pairs_pairwise(&elves))
+            );
+        }
+    }
+}
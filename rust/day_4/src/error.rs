@@ -0,0 +1,31 @@
+use std::fmt;
+
+/// Reason a range or zone of the input was rejected, located by the grammar that read it.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ParseError {
+    ReversedRange { start: String, end: String },
+    EmptyRange { range: String },
+    MismatchedDimensions { one: usize, two: usize },
+    NotAPair { elves: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::ReversedRange { start, end } => write!(
+                f,
+                "start of range {}-{} must not be after its end",
+                start, end
+            ),
+            ParseError::EmptyRange { range } => write!(f, "half-open range {} is empty", range),
+            ParseError::MismatchedDimensions { one, two } => write!(
+                f,
+                "zones of {} and {} dimensions cannot be compared",
+                one, two
+            ),
+            ParseError::NotAPair { elves } => {
+                write!(f, "expected a pair of elves, found a team of {}", elves)
+            }
+        }
+    }
+}
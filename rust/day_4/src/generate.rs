@@ -0,0 +1,30 @@
+use aoc_common::Rng;
+
+/// `amount` pairs of ranges within sections 1 to 99, like the puzzle input.
+pub fn generate(amount: usize, rng: &mut Rng) -> String {
+    let mut range = || {
+        let start = rng.between(1, 99);
+        format!("{}-{}", start, rng.between(start, 99))
+    };
+
+    let mut assignments = String::new();
+    for _ in 0..amount {
+        let line = format!("{},{}\n", range(), range());
+        assignments.push_str(&line);
+    }
+    assignments
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parse_assignment;
+
+    #[test]
+    fn test_generated_input_parses() {
+        let input = generate(50, &mut Rng::new(4));
+        assert_eq!(input.lines().count(), 50);
+        assert!(input.lines().all(|line| parse_assignment(line).is_ok()));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::range::{Range, SectionId};
+
+/// Union of ranges, kept as a sorted list of disjoint ranges with a gap between any two.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IntervalSet<T = u64> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: SectionId> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        // Everything from `first` up to `last` overlaps or touches the new range
+        let first = self
+            .ranges
+            .partition_point(|r| r.end < range.start && !r.is_adjacent(&range));
+        let last = self
+            .ranges
+            .partition_point(|r| r.start <= range.end || r.is_adjacent(&range));
+
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold(range, |merged, r| merged.union(r).unwrap());
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: &Range<T>) {
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let remainder = self.ranges[first..last]
+            .iter()
+            .flat_map(|r| r.difference(range))
+            .collect::<Vec<Range<T>>>();
+        self.ranges.splice(first..last, remainder);
+    }
+
+    pub fn covers(&self, section: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end < section);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(section))
+    }
+
+    pub fn covered_len(&self) -> u128 {
+        self.ranges.iter().map(|range| range.len()).sum()
+    }
+
+    /// Sections between the lowest and highest covered section that are not covered.
+    pub fn gaps(&self) -> Vec<Range<T>> {
+        self.ranges
+            .windows(2)
+            .map(|pair| Range {
+                start: pair[0].end.successor().unwrap(),
+                end: pair[1].start.predecessor().unwrap(),
+            })
+            .collect()
+    }
+}
+
+impl<T: SectionId> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: SectionId> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_insert_merges_overlapping_and_adjacent_ranges() {
+        let set = IntervalSet::from_iter([
+            Range::new(2, 4),
+            Range::new(10, 12),
+            Range::new(5, 6),
+            Range::new(11, 15),
+            Range::new(20, 20),
+        ]);
+        assert_eq!(
+            set.ranges(),
+            &[Range::new(2, 6), Range::new(10, 15), Range::new(20, 20)]
+        );
+        assert_eq!(set.covered_len(), 12);
+        assert_eq!(set.gaps(), vec![Range::new(7, 9), Range::new(16, 19)]);
+    }
+
+    #[test]
+    fn test_remove_splits_ranges() {
+        let mut set = IntervalSet::from_iter([Range::new(2, 8), Range::new(10, 12)]);
+        set.remove(&Range::new(4, 10));
+        assert_eq!(set.ranges(), &[Range::new(2, 3), Range::new(11, 12)]);
+        assert!(set.covers(3));
+        assert!(!set.covers(4));
+        assert!(!set.covers(13));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Insert(Range<u8>),
+        Remove(Range<u8>),
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        (any::<bool>(), 0..40u8, 0..40u8).prop_map(|(insert, a, b)| {
+            let range = Range::new(a.min(b), a.max(b));
+            if insert {
+                Operation::Insert(range)
+            } else {
+                Operation::Remove(range)
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_interval_set_matches_section_set(operations in prop::collection::vec(operation(), 0..20)) {
+            let mut set = IntervalSet::new();
+            let mut sections = BTreeSet::new();
+            for operation in operations {
+                match operation {
+                    Operation::Insert(range) => {
+                        set.insert(range);
+                        sections.extend(range.start..=range.end);
+                    }
+                    Operation::Remove(range) => {
+                        set.remove(&range);
+                        sections.retain(|section| !range.contains(*section));
+                    }
+                }
+            }
+
+            let normalized = set
+                .ranges()
+                .windows(2)
+                .all(|pair| pair[0].end < pair[1].start && !pair[0].is_adjacent(&pair[1]));
+            prop_assert!(normalized);
+            prop_assert_eq!(set.covered_len(), sections.len() as u128);
+            for section in 0..42u8 {
+                prop_assert_eq!(set.covers(section), sections.contains(&section));
+            }
+            if let (Some(&first), Some(&last)) = (sections.first(), sections.last()) {
+                let gaps = set.gaps().iter().map(|gap| gap.len()).sum::<u128>();
+                prop_assert_eq!(gaps, (last - first + 1) as u128 - sections.len() as u128);
+            }
+        }
+    }
+}
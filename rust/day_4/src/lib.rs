@@ -0,0 +1,423 @@
+use aoc_common::parse::{self, lines, map, separated_pair, Parsed};
+use aoc_common::{Rng, Solution};
+
+use crate::team::range;
+
+pub use classify::{classify, classify_all, overlap_length, OverlapClass};
+pub use coverage::{Coverage, Segment};
+pub use cross_overlap::{
+    find_overlapping_pairs, find_overlapping_pairs_pairwise, ElfId, OverlappingPair,
+};
+pub use error::ParseError;
+pub use interval_set::IntervalSet;
+pub use range::{Range, SectionId};
+pub use rebalance::{
+    diff, format_assignment, rebalance, rebalance_all, rebalanced_path, Rebalance, Rebalanced,
+};
+pub use syntax::{parse_assignment_with, parse_input_with, parse_range_with, Syntax};
+pub use team::{
+    is_any_pair_fully_contained, is_every_pair_fully_contained, is_one_range_containing_all_others,
+    parse_pairs, parse_team, parse_teams, team_have_all_ranges_overlapping,
+    team_have_any_overlapping_ranges, Team,
+};
+pub use visualize::{
+    render_strips, render_svg, section_bounds, strip, strip_columns, MAX_STRIP_ASSIGNMENTS,
+    MAX_STRIP_SECTIONS,
+};
+pub use zone::{
+    intersection_volume, is_either_zone_fully_contained, is_first_zone_contained_by_second,
+    parse_zone, parse_zone_assignment, parse_zone_assignments,
+    zone_assignment_have_overlapping_zones, zones_overlap, Zone, ZoneAssignment,
+};
+
+pub mod classify;
+pub mod coverage;
+pub mod cross_overlap;
+pub mod error;
+pub mod generate;
+pub mod interval_set;
+pub mod oracle;
+pub mod range;
+pub mod rebalance;
+pub mod syntax;
+pub mod team;
+pub mod visualize;
+pub mod zone;
+
+// A line of the input, a pair of section ranges `a-b,c-d`
+fn assignment<'a>() -> impl Fn(&'a str) -> Parsed<'a, Assignment> {
+    map(
+        separated_pair(range(), ",", range()),
+        |(assignment_one, assignment_two)| Assignment {
+            assignment_one,
+            assignment_two,
+        },
+    )
+}
+
+/// A single line of the input, with the grammar of `parse_input`.
+pub fn parse_assignment(line: &str) -> Result<Assignment, parse::Error> {
+    parse::parse(line, assignment())
+}
+
+/// Whole input as a grammar: one pair of section ranges `a-b,c-d` per line.
+pub fn parse_input(input: &str) -> Result<Vec<Assignment>, parse::Error> {
+    parse::parse(input, lines(assignment()))
+}
+
+pub fn is_either_fully_contained<T: SectionId>(assignment: &Assignment<T>) -> bool {
+    let range_one = &assignment.assignment_one;
+    let range_two = &assignment.assignment_two;
+
+    is_first_range_contained_by_second(range_one, range_two)
+        || is_first_range_contained_by_second(range_two, range_one)
+}
+
+pub fn assignment_have_overlapping_ranges<T: SectionId>(assignment: &Assignment<T>) -> bool {
+    let range_one = &assignment.assignment_one;
+    let range_two = &assignment.assignment_two;
+
+    is_start_first_range_inside_second_range(range_one, range_two)
+        || is_end_first_range_inside_second_range(range_one, range_two)
+        || is_start_first_range_inside_second_range(range_two, range_one)
+        || is_end_first_range_inside_second_range(range_two, range_one)
+}
+
+pub fn is_first_range_contained_by_second<T: SectionId>(
+    first_range: &Range<T>,
+    second_range: &Range<T>,
+) -> bool {
+    second_range.contains_range(first_range)
+}
+
+pub fn is_start_first_range_inside_second_range<T: SectionId>(
+    first_range: &Range<T>,
+    second_range: &Range<T>,
+) -> bool {
+    second_range.contains(first_range.start)
+}
+
+pub fn is_end_first_range_inside_second_range<T: SectionId>(
+    first_range: &Range<T>,
+    second_range: &Range<T>,
+) -> bool {
+    second_range.contains(first_range.end)
+}
+
+/// Sections within the span of all teams that no elf has to clean.
+pub fn sections_cleaned_by_nobody(teams: &[Team]) -> Vec<Range> {
+    teams
+        .iter()
+        .flat_map(|team| team.ranges.iter().copied())
+        .collect::<IntervalSet>()
+        .gaps()
+}
+
+/// Sections that every elf has to clean, if there are any.
+pub fn sections_cleaned_by_everyone(teams: &[Team]) -> Option<Range> {
+    let mut ranges = teams.iter().flat_map(|team| team.ranges.iter());
+    let first = *ranges.next()?;
+    ranges.try_fold(first, |common, range| common.intersection(range))
+}
+
+/// Counts the elves per section across the whole camp rather than per team.
+pub fn camp_coverage(teams: &[Team]) -> Coverage {
+    Coverage::sweep(teams.iter().flat_map(|team| team.ranges.iter().copied()))
+}
+
+/// Every elf of every team, identified by line and position.
+pub fn elves_of(teams: &[Team]) -> Vec<(ElfId, Range)> {
+    teams
+        .iter()
+        .enumerate()
+        .flat_map(|(line, team)| {
+            team.ranges
+                .iter()
+                .enumerate()
+                .map(move |(elf, &range)| (ElfId { line, elf }, range))
+        })
+        .collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<Assignment>;
+
+    fn parse(input: &str) -> Result<Vec<Assignment>, String> {
+        parse_input(input).map_err(|error| error.to_string())
+    }
+
+    fn part_one(assignments: &Vec<Assignment>) -> String {
+        let contained = assignments
+            .iter()
+            .filter(|assignment| is_either_fully_contained(assignment));
+        contained.count().to_string()
+    }
+
+    fn part_two(assignments: &Vec<Assignment>) -> String {
+        let overlapping = assignments
+            .iter()
+            .filter(|assignment| assignment_have_overlapping_ranges(assignment));
+        overlapping.count().to_string()
+    }
+
+    fn generate(amount: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate::generate(amount, rng))
+    }
+
+    fn oracle(input: &str) -> Option<Result<[String; 2], String>> {
+        Some(oracle::answers(input))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Assignment<T = u64> {
+    pub assignment_one: Range<T>,
+    pub assignment_two: Range<T>,
+}
+
+impl<T: Copy> Assignment<T> {
+    pub fn ranges(&self) -> [Range<T>; 2] {
+        [self.assignment_one, self.assignment_two]
+    }
+}
+
+// The tests moved from main.rs keep comparing with literal booleans
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+    use aoc_common::{count_allocations, Rng};
+    use proptest::prelude::*;
+
+    aoc_common::count_allocations_globally!();
+
+    #[test]
+    fn test_is_either_fully_contained_false_for_disjointed() {
+        let disjointed = Assignment {
+            assignment_one: Range { start: 2, end: 4 },
+            assignment_two: Range { start: 6, end: 8 },
+        };
+        assert_eq!(is_either_fully_contained(&disjointed), false);
+    }
+
+    #[test]
+    fn test_is_either_fully_contained_false_for_range_one_overlapping_range_two() {
+        let range_one_overlap_range_two = Assignment {
+            assignment_one: Range { start: 2, end: 4 },
+            assignment_two: Range { start: 5, end: 8 },
+        };
+        assert_eq!(
+            is_either_fully_contained(&range_one_overlap_range_two),
+            false
+        );
+    }
+
+    #[test]
+    fn test_is_either_fully_contained_false_for_range_two_overlapping_range_one() {
+        let range_two_overlap_range_one = Assignment {
+            assignment_one: Range { start: 2, end: 4 },
+            assignment_two: Range { start: 5, end: 8 },
+        };
+        assert_eq!(
+            is_either_fully_contained(&range_two_overlap_range_one),
+            false
+        );
+    }
+
+    #[test]
+    fn test_is_either_fully_contained_true_for_range_one_inside_range_two() {
+        let range_one_contained_in_range_two = Assignment {
+            assignment_one: Range { start: 4, end: 6 },
+            assignment_two: Range { start: 3, end: 8 },
+        };
+        assert_eq!(
+            is_either_fully_contained(&range_one_contained_in_range_two),
+            true
+        );
+    }
+
+    #[test]
+    fn test_is_either_fully_contained_true_for_range_two_inside_range_one() {
+        let range_two_contained_in_range_one = Assignment {
+            assignment_one: Range { start: 1, end: 4 },
+            assignment_two: Range { start: 2, end: 3 },
+        };
+        assert_eq!(
+            is_either_fully_contained(&range_two_contained_in_range_one),
+            true
+        );
+    }
+
+    #[test]
+    fn test_sections_cleaned_by_nobody_and_everyone() {
+        let teams = vec![
+            Team {
+                ranges: vec![Range { start: 2, end: 4 }, Range { start: 3, end: 6 }],
+            },
+            Team {
+                ranges: vec![
+                    Range { start: 4, end: 4 },
+                    Range { start: 9, end: 12 },
+                    Range { start: 11, end: 11 },
+                ],
+            },
+        ];
+        assert_eq!(
+            sections_cleaned_by_nobody(&teams),
+            vec![Range { start: 7, end: 8 }]
+        );
+        assert_eq!(sections_cleaned_by_everyone(&teams), None);
+        assert_eq!(
+            sections_cleaned_by_everyone(&teams[..1]),
+            Some(Range { start: 3, end: 4 })
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        let expected = vec![
+            Assignment {
+                assignment_one: Range { start: 2, end: 4 },
+                assignment_two: Range { start: 6, end: 8 },
+            },
+            Assignment {
+                assignment_one: Range { start: 2, end: 3 },
+                assignment_two: Range { start: 4, end: 5 },
+            },
+            Assignment {
+                assignment_one: Range { start: 5, end: 7 },
+                assignment_two: Range { start: 7, end: 9 },
+            },
+            Assignment {
+                assignment_one: Range { start: 2, end: 8 },
+                assignment_two: Range { start: 3, end: 7 },
+            },
+            Assignment {
+                assignment_one: Range { start: 6, end: 6 },
+                assignment_two: Range { start: 4, end: 6 },
+            },
+            Assignment {
+                assignment_one: Range { start: 2, end: 6 },
+                assignment_two: Range { start: 4, end: 8 },
+            },
+        ];
+
+        let input = fs::read_to_string("./src/test-input.txt").unwrap();
+        let actual = input
+            .lines()
+            .map(|line| parse_assignment(line).unwrap())
+            .collect::<Vec<Assignment>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(parse_input(&input), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_allocates_the_same_for_any_amount_of_lines() {
+        for amount in [10, 10_000] {
+            let input = generate::generate(amount, &mut Rng::new(4));
+            let (parsed, allocations) = count_allocations(|| Day4::parse(&input));
+            assert!(parsed.is_ok());
+            assert!(
+                allocations <= 1,
+                "{} allocations for {} assignments",
+                allocations,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_input_locates_errors() {
+        let error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            error("2-4,6-8\n2-4,6-x\n"),
+            "line 2, column 7: expected a number, found 'x'"
+        );
+        assert_eq!(
+            error("2-4,6-8,3-5"),
+            "line 1, column 8: expected end of line, found ','"
+        );
+        assert_eq!(
+            error("2-4"),
+            "line 1, column 4: expected ',', found end of input"
+        );
+        assert_eq!(
+            error("2-4,6-8\n6-8,4-2\n"),
+            format!(
+                "line 2, column 5: {}",
+                ParseError::ReversedRange {
+                    start: "4".to_string(),
+                    end: "2".to_string()
+                }
+            )
+        );
+    }
+
+    fn range() -> impl Strategy<Value = Range> {
+        (0..100u64, 0..100u64).prop_map(|(a, b)| Range::new(a.min(b), a.max(b)))
+    }
+
+    fn assignment() -> impl Strategy<Value = Assignment> {
+        (range(), range()).prop_map(|(assignment_one, assignment_two)| Assignment {
+            assignment_one,
+            assignment_two,
+        })
+    }
+
+    fn swapped(assignment: &Assignment) -> Assignment {
+        Assignment {
+            assignment_one: assignment.assignment_two,
+            assignment_two: assignment.assignment_one,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_overlap_is_symmetric(assignment in assignment()) {
+            prop_assert_eq!(
+                assignment_have_overlapping_ranges(&assignment),
+                assignment_have_overlapping_ranges(&swapped(&assignment))
+            );
+            prop_assert_eq!(
+                is_either_fully_contained(&assignment),
+                is_either_fully_contained(&swapped(&assignment))
+            );
+        }
+
+        #[test]
+        fn test_full_containment_implies_overlap(assignment in assignment()) {
+            if is_either_fully_contained(&assignment) {
+                prop_assert!(assignment_have_overlapping_ranges(&assignment));
+            }
+        }
+
+        #[test]
+        fn test_range_contains_itself(range in range()) {
+            prop_assert!(is_first_range_contained_by_second(&range, &range));
+        }
+
+        #[test]
+        fn test_parse_assignment_round_trips(assignment in assignment()) {
+            prop_assert_eq!(parse_assignment(&format_assignment(&assignment)), Ok(assignment));
+            prop_assert_eq!(parse_input(&format_assignment(&assignment)), Ok(vec![assignment]));
+        }
+
+        #[test]
+        fn test_classes_mirror_when_swapped(assignment in assignment()) {
+            let mirrored = match classify(&assignment) {
+                OverlapClass::PartialLeft => OverlapClass::PartialRight,
+                OverlapClass::PartialRight => OverlapClass::PartialLeft,
+                OverlapClass::OneContainsTwo => OverlapClass::TwoContainsOne,
+                OverlapClass::TwoContainsOne => OverlapClass::OneContainsTwo,
+                class => class,
+            };
+            prop_assert_eq!(classify(&swapped(&assignment)), mirrored);
+        }
+    }
+}
@@ -1,235 +1,342 @@
-use std::fs::File;
-use std::io::{self, BufRead, BufReader, Lines};
-use std::path::Path;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_common::{read_input, Input};
+use day_4::{
+    assignment_have_overlapping_ranges, camp_coverage, classify_all, diff, elves_of,
+    find_overlapping_pairs, format_assignment, intersection_volume, is_any_pair_fully_contained,
+    is_either_fully_contained, is_either_zone_fully_contained, is_every_pair_fully_contained,
+    is_one_range_containing_all_others, overlap_length, parse_input_with, parse_pairs, parse_teams,
+    parse_zone_assignments, rebalance_all, rebalanced_path, render_strips, render_svg,
+    section_bounds, sections_cleaned_by_everyone, sections_cleaned_by_nobody, strip_columns,
+    team_have_all_ranges_overlapping, team_have_any_overlapping_ranges,
+    zone_assignment_have_overlapping_zones, Assignment, Range, Syntax, Team, ZoneAssignment,
+    MAX_STRIP_ASSIGNMENTS, MAX_STRIP_SECTIONS,
+};
 
 fn main() {
-    let assignments = read_lines("./src/input.txt")
-        .unwrap()
-        .map(|line| parse_assignment(&line.unwrap()))
-        .collect::<Vec<Assignment>>();
+    let mut args = env::args().collect::<Vec<String>>();
+    if let Err(error) = run(&mut args) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
 
-    let overlapping = assignments
+fn run(args: &mut Vec<String>) -> Result<(), String> {
+    let (source, input) = read_input(args, "./src/input.txt")?;
+    let lines = input.lines().collect::<Vec<&str>>();
+    // Parsed on demand, the extended syntax is not valid for teams
+    let teams = || parse_teams(&input).map_err(|error| error.to_string());
+    // Teams are read as well, so a line of three elves is reported as such
+    let pairs = |mode: &str| {
+        parse_pairs(&input)
+            .map_err(|error| format!("Mode '{}' needs pairs of elves, {}", mode, error))
+    };
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        None => print_overlaps(&teams()?),
+        Some("teams") => print_team_overlaps(&teams()?),
+        Some("sections") => print_sections(&teams()?),
+        Some("coverage") => print_coverage(&teams()?),
+        Some("cross-overlaps") => {
+            print_cross_overlaps(&teams()?, args.get(2).is_some_and(|arg| arg == "--list"))
+        }
+        Some("rebalance") => {
+            let output_path = match (args.get(2), &source) {
+                (Some(path), _) => PathBuf::from(path),
+                (None, Input::Path(path)) => rebalanced_path(Path::new(path)),
+                (None, _) => {
+                    return Err(format!(
+                        "Rebalancing {} needs a path to write the result to",
+                        source
+                    ))
+                }
+            };
+            write_rebalanced(&pairs("rebalance")?, &source, &output_path)?
+        }
+        Some("classify") => print_classes(
+            &pairs("classify")?,
+            &lines,
+            args.get(2).is_some_and(|arg| arg == "--list"),
+        ),
+        Some("visualize") => visualize(
+            &pairs("visualize")?,
+            args.get(2).map_or("./input.svg", |arg| arg.as_str()),
+        )?,
+        Some("extended") => {
+            let syntax = Syntax {
+                extended: true,
+                normalize_reversed: args.get(2).is_some_and(|arg| arg == "--normalize"),
+            };
+            print_extended_overlaps(
+                &parse_input_with(&input, &syntax).map_err(|error| error.to_string())?,
+            )
+        }
+        Some("zones") => {
+            print_zone_overlaps(&parse_zone_assignments(&input).map_err(|error| error.to_string())?)
+        }
+        Some(other) => return Err(format!("Unknown mode '{}'", other)),
+    }
+    Ok(())
+}
+
+fn print_overlaps(teams: &[Team]) {
+    let overlapping = teams
         .iter()
-        .filter(|assignment| is_either_fully_contained(assignment))
-        .collect::<Vec<&Assignment>>();
+        .filter(|team| is_any_pair_fully_contained(team))
+        .collect::<Vec<&Team>>();
 
-    println!("Number of completely overlapping assignments: {}", overlapping.len());
+    println!(
+        "Number of completely overlapping assignments: {}",
+        overlapping.len()
+    );
 
-    let partial_overlapping = assignments
+    let partial_overlapping = teams
         .iter()
-        .filter(|assignment| assignment_have_overlapping_ranges(assignment))
-        .collect::<Vec<&Assignment>>();
+        .filter(|team| team_have_any_overlapping_ranges(team))
+        .collect::<Vec<&Team>>();
 
-    println!("Number of partially overlapping assignments: {}", partial_overlapping.len());
+    println!(
+        "Number of partially overlapping assignments: {}",
+        partial_overlapping.len()
+    );
 }
 
-fn parse_assignment(line: &String) -> Assignment {
-    let ranges = line.split(",").collect::<Vec<&str>>();
+fn print_team_overlaps(teams: &[Team]) {
+    let count = |predicate: fn(&Team) -> bool| teams.iter().filter(|team| predicate(team)).count();
+
+    println!(
+        "Number of teams with any pair fully contained: {}",
+        count(is_any_pair_fully_contained)
+    );
+    println!(
+        "Number of teams with all pairs fully contained: {}",
+        count(is_every_pair_fully_contained)
+    );
+    println!(
+        "Number of teams with one range containing all others: {}",
+        count(is_one_range_containing_all_others)
+    );
+    println!(
+        "Number of teams with any pair overlapping: {}",
+        count(team_have_any_overlapping_ranges)
+    );
+    println!(
+        "Number of teams with all pairs overlapping: {}",
+        count(team_have_all_ranges_overlapping)
+    );
+}
 
-    if ranges.len() != 2 {
-        panic!("Each row must contain two assignments. Given line contains a different amount.\nAmount '{}'\nLine '{}'\n", ranges.len(), line);
+fn print_sections(teams: &[Team]) {
+    let unassigned = sections_cleaned_by_nobody(teams);
+    println!(
+        "Sections cleaned by nobody: {}",
+        unassigned.iter().map(Range::len).sum::<u128>()
+    );
+    for range in unassigned {
+        println!("  {}-{}", range.start, range.end);
     }
 
-    let range_one = ranges[0].split("-").collect::<Vec<&str>>();
-    let range_two = ranges[1].split("-").collect::<Vec<&str>>();
-
-    if range_one.len() != 2 {
-        panic!(
-            "A range must contain two parts.\nAmount '{}'\n",
-            range_one.len()
-        );
+    match sections_cleaned_by_everyone(teams) {
+        Some(range) => println!(
+            "Sections cleaned by everyone: {}-{}",
+            range.start, range.end
+        ),
+        None => println!("Sections cleaned by everyone: none"),
     }
+}
 
-    if range_two.len() != 2 {
-        panic!(
-            "A range must contain two parts.\nAmount '{}'\n",
-            range_two.len()
-        );
-    }
+fn print_coverage(teams: &[Team]) {
+    let coverage = camp_coverage(teams);
 
-    let start_range_one = range_one[0].parse::<u64>().unwrap();
-    let end_range_one = range_one[1].parse::<u64>().unwrap();
-    if start_range_one > end_range_one {
-        panic!(
-            "Start of range one must be before end\nStart: '{}'\nEnd: '{}'",
-            start_range_one, end_range_one
+    println!("Elves per section:");
+    for segment in coverage.segments() {
+        println!(
+            "  {}-{}: {}",
+            segment.range.start, segment.range.end, segment.elves
         );
     }
 
-    let start_range_two = range_two[0].parse::<u64>().unwrap();
-    let end_range_two = range_two[1].parse::<u64>().unwrap();
-    if start_range_two > end_range_two {
-        panic!(
-            "Start of range two must be before end\nStart: '{}'\nEnd: '{}'",
-            start_range_two, end_range_two
+    println!("Maximum simultaneous overlap: {}", coverage.max_overlap());
+
+    println!("Most over-assigned sections:");
+    for segment in coverage.most_assigned(5) {
+        println!(
+            "  {}-{}: {}",
+            segment.range.start, segment.range.end, segment.elves
         );
     }
 
-    return Assignment {
-        assignment_one: Range {
-            start: start_range_one,
-            end: end_range_one,
-        },
-        assignment_two: Range {
-            start: start_range_two,
-            end: end_range_two,
-        },
-    };
-}
-
-fn is_either_fully_contained(assignment: &Assignment) -> bool {
-    let range_one = &assignment.assignment_one;
-    let range_two = &assignment.assignment_two;
-
-    return is_first_range_contained_by_second(range_one, range_two)
-        || is_first_range_contained_by_second(range_two, range_one);
-}
-
-fn assignment_have_overlapping_ranges (assignment: &Assignment) -> bool {
-    let range_one = &assignment.assignment_one;
-    let range_two = &assignment.assignment_two;
-
-    return is_start_first_range_inside_second_range(range_one, range_two)
-        || is_end_first_range_inside_second_range(range_one, range_two)
-
-        || is_start_first_range_inside_second_range(range_two, range_one)
-        || is_end_first_range_inside_second_range(range_two, range_one)
-        ;
-}
-
-fn is_first_range_contained_by_second(first_range: &Range, second_range: &Range) -> bool {
-    let start_first_range_inside_second_range: bool = is_start_first_range_inside_second_range(first_range, second_range);
-    let end_first_range_inside_second_range: bool = is_end_first_range_inside_second_range(first_range, second_range);
-
-    return start_first_range_inside_second_range && end_first_range_inside_second_range;
+    let unassigned = coverage.unassigned();
+    println!(
+        "Unassigned sections: {}",
+        unassigned.iter().map(Range::len).sum::<u128>()
+    );
+    for range in unassigned {
+        println!("  {}-{}", range.start, range.end);
+    }
 }
 
-fn is_start_first_range_inside_second_range (first_range: &Range, second_range: &Range) -> bool {
-    return second_range.start <= first_range.start && first_range.start <= second_range.end;
+fn print_cross_overlaps(teams: &[Team], list: bool) {
+    let mut pairs = find_overlapping_pairs(&elves_of(teams));
+    pairs.sort_by_key(|pair| (pair.first, pair.second));
+
+    let fully_contained = pairs.iter().filter(|pair| pair.fully_contained).count();
+    println!("Number of overlapping pairs of elves: {}", pairs.len());
+    println!(
+        "Number of fully contained pairs of elves: {}",
+        fully_contained
+    );
+
+    if list {
+        for pair in pairs {
+            println!(
+                "  line {} elf {} / line {} elf {}{}",
+                pair.first.line + 1,
+                pair.first.elf + 1,
+                pair.second.line + 1,
+                pair.second.elf + 1,
+                if pair.fully_contained {
+                    " (contained)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
 }
 
-fn is_end_first_range_inside_second_range (first_range: &Range, second_range: &Range) -> bool {
-    return second_range.start <= first_range.end && first_range.end <= second_range.end;
-}
+fn write_rebalanced(
+    assignments: &[Assignment],
+    source: &Input,
+    output_path: &Path,
+) -> Result<(), String> {
+    let rebalanced = rebalance_all(assignments);
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+    let content = rebalanced
+        .assignments
+        .iter()
+        .map(|assignment| format_assignment(assignment) + "\n")
+        .collect::<String>();
+    fs::write(output_path, content)
+        .map_err(|error| format!("{}: {}", output_path.display(), error))?;
+
+    print!(
+        "{}",
+        diff(
+            &source.to_string(),
+            &output_path.display().to_string(),
+            assignments,
+            &rebalanced.assignments
+        )
+    );
+    println!(
+        "Number of rebalanced assignments: {}",
+        assignments
+            .iter()
+            .zip(&rebalanced.assignments)
+            .filter(|(before, after)| before != after)
+            .count()
+    );
+    println!(
+        "Number of unresolvable assignments: {}",
+        rebalanced.unresolvable.len()
+    );
+    Ok(())
 }
 
-#[derive(PartialEq, Debug)]
-struct Assignment {
-    assignment_one: Range,
-    assignment_two: Range,
-}
+fn print_zone_overlaps(assignments: &[ZoneAssignment]) {
+    let overlapping = assignments
+        .iter()
+        .filter(|assignment| is_either_zone_fully_contained(assignment))
+        .count();
+    println!(
+        "Number of completely overlapping zone assignments: {}",
+        overlapping
+    );
 
-#[derive(PartialEq, Debug)]
-struct Range {
-    start: u64,
-    end: u64,
+    let partial_overlapping = assignments
+        .iter()
+        .filter(|assignment| zone_assignment_have_overlapping_zones(assignment))
+        .count();
+    println!(
+        "Number of partially overlapping zone assignments: {}",
+        partial_overlapping
+    );
+
+    let volume = assignments
+        .iter()
+        .map(|assignment| intersection_volume(&assignment.zone_one, &assignment.zone_two))
+        .fold(0, |total: u128, volume| total.saturating_add(volume));
+    println!("Total volume cleaned twice: {}", volume);
 }
 
-#[cfg(test)]
-mod tests {
-
-    use super::*;
-
-    #[test]
-    fn test_is_either_fully_contained_false_for_disjointed() {
-        let disjointed = Assignment {
-            assignment_one: Range { start: 2, end: 4 },
-            assignment_two: Range { start: 6, end: 8 },
-        };
-        assert_eq!(is_either_fully_contained(&disjointed), false);
-    }
-
-    #[test]
-    fn test_is_either_fully_contained_false_for_range_one_overlapping_range_two() {
-        let range_one_overlap_range_two = Assignment {
-            assignment_one: Range { start: 2, end: 4 },
-            assignment_two: Range { start: 5, end: 8 },
-        };
-        assert_eq!(
-            is_either_fully_contained(&range_one_overlap_range_two),
-            false
-        );
+fn print_classes(assignments: &[Assignment], lines: &[&str], list: bool) {
+    let classes = classify_all(assignments);
+    for (class, indices) in &classes {
+        println!("Number of {} assignments: {}", class, indices.len());
     }
 
-    #[test]
-    fn test_is_either_fully_contained_false_for_range_two_overlapping_range_one() {
-        let range_two_overlap_range_one = Assignment {
-            assignment_one: Range { start: 2, end: 4 },
-            assignment_two: Range { start: 5, end: 8 },
-        };
-        assert_eq!(
-            is_either_fully_contained(&range_two_overlap_range_one),
-            false
-        );
+    let total_overlap: u128 = assignments.iter().map(overlap_length).sum();
+    println!("Total overlap length: {}", total_overlap);
+
+    if list {
+        for (class, indices) in &classes {
+            println!("{}:", class);
+            for &index in indices {
+                println!(
+                    "  line {}: {} (overlap {})",
+                    index + 1,
+                    lines[index],
+                    overlap_length(&assignments[index])
+                );
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_is_either_fully_contained_true_for_range_one_inside_range_two() {
-        let range_one_contained_in_range_two = Assignment {
-            assignment_one: Range { start: 4, end: 6 },
-            assignment_two: Range { start: 3, end: 8 },
-        };
-        assert_eq!(
-            is_either_fully_contained(&range_one_contained_in_range_two),
-            true
-        );
-    }
+fn visualize(assignments: &[Assignment], svg_path: &str) -> Result<(), String> {
+    let sections = match section_bounds(assignments) {
+        Some(sections) => sections,
+        None => {
+            println!("No assignments to visualize");
+            return Ok(());
+        }
+    };
 
-    #[test]
-    fn test_is_either_fully_contained_true_for_range_two_inside_range_one() {
-        let range_two_contained_in_range_one = Assignment {
-            assignment_one: Range { start: 1, end: 4 },
-            assignment_two: Range { start: 2, end: 3 },
-        };
-        assert_eq!(
-            is_either_fully_contained(&range_two_contained_in_range_one),
-            true
+    if strip_columns(&sections).len() <= MAX_STRIP_SECTIONS
+        && assignments.len() <= MAX_STRIP_ASSIGNMENTS
+    {
+        print!("{}", render_strips(assignments, &sections));
+    } else {
+        fs::write(svg_path, render_svg(assignments, &sections))
+            .map_err(|error| format!("{}: {}", svg_path, error))?;
+        println!(
+            "{} assignments over sections {}-{} are too large for text, wrote chart to {}",
+            assignments.len(),
+            sections.start,
+            sections.end,
+            svg_path
         );
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_parse_assignment() {
-        let expected = vec![
-            Assignment {
-                assignment_one: Range { start: 2, end: 4 },
-                assignment_two: Range { start: 6, end: 8 },
-            },
-            Assignment {
-                assignment_one: Range { start: 2, end: 3 },
-                assignment_two: Range { start: 4, end: 5 },
-            },
-            Assignment {
-                assignment_one: Range { start: 5, end: 7 },
-                assignment_two: Range { start: 7, end: 9 },
-            },
-            Assignment {
-                assignment_one: Range { start: 2, end: 8 },
-                assignment_two: Range { start: 3, end: 7 },
-            },
-            Assignment {
-                assignment_one: Range { start: 6, end: 6 },
-                assignment_two: Range { start: 4, end: 6 },
-            },
-            Assignment {
-                assignment_one: Range { start: 2, end: 6 },
-                assignment_two: Range { start: 4, end: 8 },
-            },
-        ];
-
-        let actual = read_lines("./src/test-input.txt")
-            .unwrap()
-            .map(|line| parse_assignment(&line.unwrap()))
-            .collect::<Vec<Assignment>>();
-
-        assert_eq!(actual, expected);
-    }
+fn print_extended_overlaps(assignments: &[Assignment<i64>]) {
+    println!(
+        "Number of completely overlapping assignments: {}",
+        assignments
+            .iter()
+            .filter(|assignment| is_either_fully_contained(assignment))
+            .count()
+    );
+    println!(
+        "Number of partially overlapping assignments: {}",
+        assignments
+            .iter()
+            .filter(|assignment| assignment_have_overlapping_ranges(assignment))
+            .count()
+    );
 }
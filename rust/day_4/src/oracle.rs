@@ -0,0 +1,46 @@
+use std::collections::BTreeSet;
+
+/// Naive answers to both parts, for differential testing against the solution.
+///
+/// Materializes the sections of every range as a set and compares the sets.
+pub fn answers(input: &str) -> Result<[String; 2], String> {
+    let mut part_one = 0;
+    let mut part_two = 0;
+    for (index, line) in input.lines().enumerate() {
+        let not_a_pair = || format!("line {}: '{}' is not a pair of ranges", index + 1, line);
+        let (one, two) = line.split_once(',').ok_or_else(not_a_pair)?;
+        let one = sections(one).ok_or_else(not_a_pair)?;
+        let two = sections(two).ok_or_else(not_a_pair)?;
+
+        if one.is_subset(&two) || two.is_subset(&one) {
+            part_one += 1;
+        }
+        if !one.is_disjoint(&two) {
+            part_two += 1;
+        }
+    }
+    Ok([part_one.to_string(), part_two.to_string()])
+}
+
+// None unless the range is two numbers in order
+fn sections(range: &str) -> Option<BTreeSet<u64>> {
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?);
+    (start <= end).then(|| (start..=end).collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        assert_eq!(answers(input), Ok(["2".to_string(), "4".to_string()]));
+        assert_eq!(
+            answers("2-4,6-8\n4-2,6-8\n"),
+            Err("line 2: '4-2,6-8' is not a pair of ranges".to_string())
+        );
+    }
+}
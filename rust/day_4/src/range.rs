@@ -0,0 +1,233 @@
+use std::fmt::Debug;
+
+/// Integer types that can be used as section IDs of a `Range`.
+pub trait SectionId: Copy + Ord + Debug {
+    fn successor(self) -> Option<Self>;
+    fn predecessor(self) -> Option<Self>;
+    /// Number of section IDs from `self` up to and including `end`
+    fn count_to(self, end: Self) -> u128;
+}
+
+macro_rules! impl_section_id {
+    ($($id:ty),*) => {
+        $(
+            impl SectionId for $id {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count_to(self, end: Self) -> u128 {
+                    (end as i128 - self as i128 + 1) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_section_id!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Closed interval of section IDs, both `start` and `end` are part of the range.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub struct Range<T = u64> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: SectionId> Range<T> {
+    pub fn new(start: T, end: T) -> Range<T> {
+        if start > end {
+            panic!(
+                "Start of range must be before end\nStart: '{:?}'\nEnd: '{:?}'",
+                start, end
+            );
+        }
+        Range { start, end }
+    }
+
+    pub fn len(&self) -> u128 {
+        self.start.count_to(self.end)
+    }
+
+    // A closed range always contains at least its start
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, section: T) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    pub fn contains_range(&self, other: &Range<T>) -> bool {
+        self.contains(other.start) && self.contains(other.end)
+    }
+
+    pub fn overlaps(&self, other: &Range<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// True if the ranges do not overlap but no section lies between them.
+    pub fn is_adjacent(&self, other: &Range<T>) -> bool {
+        self.end.successor() == Some(other.start) || other.end.successor() == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Range<T>) -> Option<Range<T>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Range {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// Joins both ranges, provided they overlap or are adjacent so the result has no gap.
+    pub fn union(&self, other: &Range<T>) -> Option<Range<T>> {
+        if !self.overlaps(other) && !self.is_adjacent(other) {
+            return None;
+        }
+        Some(Range {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// Sections of `self` not covered by `other`, as up to two ranges in ascending order.
+    pub fn difference(&self, other: &Range<T>) -> Vec<Range<T>> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+
+        let mut remainder = Vec::new();
+        if self.start < other.start {
+            // other.start > self.start, so it has a predecessor
+            remainder.push(Range {
+                start: self.start,
+                end: other.start.predecessor().unwrap(),
+            });
+        }
+        if other.end < self.end {
+            remainder.push(Range {
+                start: other.end.successor().unwrap(),
+                end: self.end,
+            });
+        }
+        remainder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn sections<T: SectionId + Into<i16>>(range: &Range<T>) -> BTreeSet<i16> {
+        (range.start.into()..=range.end.into()).collect()
+    }
+
+    fn is_contiguous(sections: &BTreeSet<i16>) -> bool {
+        match (sections.first(), sections.last()) {
+            (Some(first), Some(last)) => (last - first + 1) as usize == sections.len(),
+            _ => false,
+        }
+    }
+
+    fn check_against_sets<T: SectionId + Into<i16>>(one: Range<T>, two: Range<T>, section: T) {
+        let set_one = sections(&one);
+        let set_two = sections(&two);
+        let intersection = set_one.intersection(&set_two).cloned().collect();
+        let union = set_one.union(&set_two).cloned().collect();
+        let difference = set_one
+            .difference(&set_two)
+            .cloned()
+            .collect::<BTreeSet<i16>>();
+
+        assert_eq!(one.len(), set_one.len() as u128);
+        assert_eq!(one.contains(section), set_one.contains(&section.into()));
+        assert_eq!(one.contains_range(&two), set_two.is_subset(&set_one));
+        assert_eq!(one.overlaps(&two), !set_one.is_disjoint(&set_two));
+        assert_eq!(
+            one.is_adjacent(&two),
+            set_one.is_disjoint(&set_two) && is_contiguous(&union)
+        );
+
+        match one.intersection(&two) {
+            Some(range) => assert_eq!(sections(&range), intersection),
+            None => assert!(intersection.is_empty()),
+        }
+
+        match one.union(&two) {
+            Some(range) => assert_eq!(sections(&range), union),
+            None => assert!(!is_contiguous(&union)),
+        }
+
+        let remainder = one.difference(&two);
+        assert!(remainder.len() <= 2);
+        assert!(remainder.windows(2).all(|pair| pair[0].end < pair[1].start));
+        assert_eq!(
+            remainder
+                .iter()
+                .flat_map(sections)
+                .collect::<BTreeSet<i16>>(),
+            difference
+        );
+    }
+
+    fn range<T: SectionId + Debug>(
+        bounds: impl Strategy<Value = (T, T)>,
+    ) -> impl Strategy<Value = Range<T>> {
+        bounds.prop_map(|(a, b)| Range::new(a.min(b), a.max(b)))
+    }
+
+    proptest! {
+        #[test]
+        fn test_signed_range_matches_section_sets(
+            one in range((any::<i8>(), any::<i8>())),
+            two in range((any::<i8>(), any::<i8>())),
+            section in any::<i8>(),
+        ) {
+            check_against_sets(one, two, section);
+        }
+
+        #[test]
+        fn test_unsigned_range_matches_section_sets(
+            one in range((any::<u8>(), any::<u8>())),
+            two in range((any::<u8>(), any::<u8>())),
+            section in any::<u8>(),
+        ) {
+            check_against_sets(one, two, section);
+        }
+
+        #[test]
+        fn test_small_range_matches_section_sets(
+            one in range((0..12u8, 0..12u8)),
+            two in range((0..12u8, 0..12u8)),
+            section in 0..12u8,
+        ) {
+            check_against_sets(one, two, section);
+        }
+    }
+
+    #[test]
+    fn test_len_of_full_domain() {
+        assert_eq!(Range::new(u64::MIN, u64::MAX).len(), 1 << 64);
+        assert_eq!(Range::new(i64::MIN, i64::MAX).len(), 1 << 64);
+    }
+
+    #[test]
+    fn test_is_adjacent_at_domain_bounds() {
+        assert!(Range::new(0u8, 254).is_adjacent(&Range::new(255, 255)));
+        assert!(!Range::new(0u8, 255).is_adjacent(&Range::new(0, 0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_rejects_reversed_range() {
+        Range::new(5, 3);
+    }
+}
@@ -0,0 +1,598 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::{Path, PathBuf};
+
+use crate::cross_overlap::ElfId;
+use crate::range::Range;
+use crate::Assignment;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Rebalance {
+    /// The ranges do not overlap, nothing to fix.
+    Unchanged,
+    /// Both elves were assigned the same single section, which cannot be split.
+    Unresolvable,
+    Adjusted(Assignment),
+}
+
+/// Splits the sections of an overlapping pair between both elves so no section is cleaned
+/// twice, while every section the pair covered stays covered. Overlap with the elves of other
+/// assignments is left alone, see `rebalance_all` for that.
+///
+/// Of all splits the one changing the range lengths the least is chosen. Ties are broken by
+/// the number of sections an elf gains or loses, then by keeping the elves in their order.
+pub fn rebalance(assignment: &Assignment) -> Rebalance {
+    let (one, two) = (assignment.assignment_one, assignment.assignment_two);
+    let union = match one.overlaps(&two) {
+        true => one.union(&two).unwrap(),
+        false => return Rebalance::Unchanged,
+    };
+    if union.len() < 2 {
+        return Rebalance::Unresolvable;
+    }
+
+    let mut best: Option<((u128, u128), Assignment)> = None;
+    for (left, right, one_is_left) in [(one, two, true), (two, one, false)] {
+        for left_len in split_candidates(&union, &left, &right) {
+            let new_left = Range::new(union.start, union.start + left_len as u64 - 1);
+            let new_right = Range::new(new_left.end + 1, union.end);
+            let cost = (
+                new_left.len().abs_diff(left.len()) + new_right.len().abs_diff(right.len()),
+                moved_sections(&new_left, &left) + moved_sections(&new_right, &right),
+            );
+            if best.is_some_and(|(best_cost, _)| best_cost <= cost) {
+                continue;
+            }
+
+            let adjusted = match one_is_left {
+                true => Assignment {
+                    assignment_one: new_left,
+                    assignment_two: new_right,
+                },
+                false => Assignment {
+                    assignment_one: new_right,
+                    assignment_two: new_left,
+                },
+            };
+            best = Some((cost, adjusted));
+        }
+    }
+    Rebalance::Adjusted(best.unwrap().1)
+}
+
+/// Outcome of rebalancing all assignments together.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Rebalanced {
+    pub assignments: Vec<Assignment>,
+    /// Lines with an elf in a group that has fewer sections than elves, these are only
+    /// rebalanced within their own pair.
+    pub unresolvable: Vec<usize>,
+}
+
+/// Adjusts the ranges of all assignments together, so no section is cleaned by two elves of
+/// any assignments, while every section covered before stays covered.
+///
+/// Elves overlapping each other, directly or through others, form a group whose sections are
+/// split between them. The total change in range length can be no smaller than the number of
+/// sections the group cleans twice, which is reached by only ever shrinking ranges. Which elf
+/// gives up which sections is decided from left to right. A group with more elves than
+/// sections cannot be split, its pairs are rebalanced on their own like `rebalance` does.
+///
+/// Of the splits with the least change, one keeping every elf inside its original range is
+/// preferred. Only when there is none, as for nested ranges like `2-8,3-7` which becomes
+/// `2-7,8-8`, are elves moved to sections they did not have to clean before.
+pub fn rebalance_all(assignments: &[Assignment]) -> Rebalanced {
+    let mut elves = assignments
+        .iter()
+        .enumerate()
+        .flat_map(|(line, assignment)| {
+            let ranges = assignment.ranges();
+            (0..2).map(move |elf| (ranges[elf], ElfId { line, elf }))
+        })
+        .collect::<Vec<(Range, ElfId)>>();
+    elves.sort_by_key(|&(range, id)| (range.start, range.end, id));
+
+    let mut adjusted = assignments
+        .iter()
+        .map(|assignment| assignment.ranges())
+        .collect::<Vec<[Range; 2]>>();
+    let mut unresolvable = Vec::new();
+    let mut rest = &elves[..];
+    while let Some((first, _)) = rest.first() {
+        let mut group_end = first.end;
+        let size = rest
+            .iter()
+            .take_while(|(range, _)| {
+                let overlapping = range.start <= group_end;
+                group_end = group_end.max(range.end);
+                overlapping
+            })
+            .count();
+        let (group, others) = rest.split_at(size);
+        match split_group(group) {
+            Some(ranges) => {
+                for (&(_, id), range) in group.iter().zip(ranges) {
+                    adjusted[id.line][id.elf] = range;
+                }
+            }
+            // Overlap with other assignments has to stay, so only each pair is split within itself
+            None => {
+                for &(_, id) in group {
+                    if let Rebalance::Adjusted(pair) = rebalance(&assignments[id.line]) {
+                        adjusted[id.line] = pair.ranges();
+                    }
+                    unresolvable.push(id.line);
+                }
+            }
+        }
+        rest = others;
+    }
+    unresolvable.sort();
+    unresolvable.dedup();
+
+    Rebalanced {
+        assignments: adjusted
+            .into_iter()
+            .map(|[assignment_one, assignment_two]| Assignment {
+                assignment_one,
+                assignment_two,
+            })
+            .collect(),
+        unresolvable,
+    }
+}
+
+// Tiles the span of the group with its elves, none of them growing. A split keeping every elf
+// inside its range is used when there is one, otherwise the elves are tiled in order, though
+// they may then move past the end of their original range. Both always succeed when every elf
+// has a section, so there is a split unless the group has more elves than sections.
+fn split_group(group: &[(Range, ElfId)]) -> Option<Vec<Range>> {
+    let start = group[0].0.start as u128;
+    let end = group.iter().map(|(range, _)| range.end).max()? as u128;
+    if group.len() as u128 > end - start + 1 {
+        return None;
+    }
+    Some(split_within_ranges(group, start, end).unwrap_or_else(|| tile_in_order(group, start, end)))
+}
+
+// Each elf ends as close to its original end as possible, while leaving at least a section to
+// every elf after it and no more sections than those elves can take.
+fn tile_in_order(group: &[(Range, ElfId)], start: u128, end: u128) -> Vec<Range> {
+    let mut capacity_after = group.iter().map(|(range, _)| range.len()).sum::<u128>();
+    let mut cursor = start;
+    let mut ranges = Vec::with_capacity(group.len());
+    for (index, (range, _)) in group.iter().enumerate() {
+        capacity_after -= range.len();
+        let elves_after = (group.len() - index - 1) as u128;
+        let lowest_end = cursor.max(end.saturating_sub(capacity_after));
+        let highest_end = (cursor + range.len() - 1).min(end - elves_after);
+        let new_end = (range.end as u128).clamp(lowest_end, highest_end);
+        ranges.push(Range::new(cursor as u64, new_end as u64));
+        cursor = new_end + 1;
+    }
+    ranges
+}
+
+// The elves start their parts in a single sweep from the start of the group. An elf starts on
+// a section when the elf started before it cannot clean it, or when the section is the latest
+// one it can start on. Of the elves whose range has begun, the one with the earliest latest
+// start goes first. Once the order is known, every elf keeps as many of its sections as the
+// elves after it leave, so earlier elves keep their original ends.
+fn split_within_ranges(group: &[(Range, ElfId)], start: u128, end: u128) -> Option<Vec<Range>> {
+    let bounds = |index: usize| (group[index].0.start as u128, group[index].0.end as u128);
+    let latest = latest_starts(group)?;
+    let mut by_latest = (0..group.len()).collect::<Vec<usize>>();
+    by_latest.sort_by_key(|&index| latest[index]);
+
+    // The group is sorted by start already
+    let mut waiting = BinaryHeap::new();
+    let mut started = vec![false; group.len()];
+    let mut order = Vec::with_capacity(group.len());
+    let (mut section, mut next, mut next_latest) = (start, 0, 0);
+    while order.len() < group.len() {
+        while started[by_latest[next_latest]] {
+            next_latest += 1;
+        }
+        if let Some(&current) = order.last() {
+            section = (bounds(current).1 + 1).min(latest[by_latest[next_latest]]);
+        }
+        while next < group.len() && bounds(next).0 <= section {
+            waiting.push(Reverse((latest[next], next)));
+            next += 1;
+        }
+        let Reverse((_, index)) = waiting.pop()?;
+        if bounds(index).1 < section {
+            return None;
+        }
+        started[index] = true;
+        order.push(index);
+    }
+    if bounds(*order.last()?).1 < end {
+        return None;
+    }
+
+    // Latest section each elf can start on in this order, working back from the end of the group
+    let mut starts = vec![start; order.len()];
+    let mut next_start = end + 1;
+    for position in (1..order.len()).rev() {
+        next_start = (bounds(order[position - 1]).1 + 1).min(next_start - 1);
+        starts[position] = next_start;
+    }
+    let mut ranges = vec![Range::new(0, 0); group.len()];
+    for (position, &index) in order.iter().enumerate() {
+        let part_end = starts.get(position + 1).map_or(end, |next| next - 1);
+        ranges[index] = Range::new(starts[position] as u64, part_end as u64);
+    }
+    Some(ranges)
+}
+
+// Latest section each elf can start on while every elf starts on a section of its own, found
+// from the end of the group by giving each section to the elf whose range begins last
+fn latest_starts(group: &[(Range, ElfId)]) -> Option<Vec<u128>> {
+    let bounds = |index: usize| (group[index].0.start as u128, group[index].0.end as u128);
+    let mut by_end = (0..group.len()).collect::<Vec<usize>>();
+    by_end.sort_by_key(|&index| Reverse(bounds(index).1));
+
+    let mut waiting = BinaryHeap::new();
+    let mut latest = vec![0; group.len()];
+    // One past the section to give out next
+    let (mut below, mut next) = (u128::MAX, 0);
+    for _ in 0..group.len() {
+        if waiting.is_empty() {
+            below = below.min(bounds(by_end[next]).1 + 1);
+        }
+        while next < group.len() && bounds(by_end[next]).1 + 1 >= below {
+            waiting.push((bounds(by_end[next]).0, by_end[next]));
+            next += 1;
+        }
+        let (first, index) = waiting.pop()?;
+        if first >= below {
+            return None;
+        }
+        below -= 1;
+        latest[index] = below;
+    }
+    Some(latest)
+}
+
+// Both costs are piecewise linear in the length of the left range, so the optimum lies on
+// one of their breakpoints or on the bounds of the valid lengths
+fn split_candidates(union: &Range, left: &Range, right: &Range) -> Vec<u128> {
+    let offset = |section: u64| (section - union.start) as u128;
+    let max_len = union.len() - 1;
+    let mut candidates = vec![
+        1,
+        max_len,
+        left.len(),
+        union.len().saturating_sub(right.len()),
+        offset(left.start),
+        offset(left.end) + 1,
+        offset(right.start),
+        offset(right.end) + 1,
+    ];
+    for candidate in candidates.iter_mut() {
+        *candidate = (*candidate).clamp(1, max_len);
+    }
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+fn moved_sections(new: &Range, original: &Range) -> u128 {
+    let kept = new.intersection(original).map_or(0, |common| common.len());
+    new.len() + original.len() - 2 * kept
+}
+
+/// Where the rebalanced assignments read from `input` go by default: next to it, with
+/// `rebalanced` added before the extension, so `src/input.txt` becomes
+/// `src/input.rebalanced.txt`.
+pub fn rebalanced_path(input: &Path) -> PathBuf {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match input.extension() {
+        Some(extension) => format!("{}.rebalanced.{}", stem, extension.to_string_lossy()),
+        None => format!("{}.rebalanced", stem),
+    };
+    input.with_file_name(name)
+}
+
+pub fn format_assignment(assignment: &Assignment) -> String {
+    format!(
+        "{}-{},{}-{}",
+        assignment.assignment_one.start,
+        assignment.assignment_one.end,
+        assignment.assignment_two.start,
+        assignment.assignment_two.end
+    )
+}
+
+/// Line based diff in unified format, with one hunk per changed line.
+pub fn diff(
+    original_name: &str,
+    adjusted_name: &str,
+    original: &[Assignment],
+    adjusted: &[Assignment],
+) -> String {
+    let mut output = format!("--- {}\n+++ {}\n", original_name, adjusted_name);
+    for (line, (before, after)) in original.iter().zip(adjusted).enumerate() {
+        if before != after {
+            output.push_str(&format!(
+                "@@ -{} +{} @@\n-{}\n+{}\n",
+                line + 1,
+                line + 1,
+                format_assignment(before),
+                format_assignment(after)
+            ));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn assignment(one: (u64, u64), two: (u64, u64)) -> Assignment {
+        Assignment {
+            assignment_one: Range::new(one.0, one.1),
+            assignment_two: Range::new(two.0, two.1),
+        }
+    }
+
+    #[test]
+    fn test_rebalance_leaves_disjoint_assignments() {
+        assert_eq!(rebalance(&assignment((2, 4), (6, 8))), Rebalance::Unchanged);
+        assert_eq!(rebalance(&assignment((2, 3), (4, 5))), Rebalance::Unchanged);
+        assert_eq!(
+            rebalance(&assignment((6, 6), (6, 6))),
+            Rebalance::Unresolvable
+        );
+    }
+
+    #[test]
+    fn test_rebalance_trims_partial_overlap() {
+        assert_eq!(
+            rebalance(&assignment((5, 7), (7, 9))),
+            Rebalance::Adjusted(assignment((5, 6), (7, 9)))
+        );
+        assert_eq!(
+            rebalance(&assignment((4, 8), (2, 6))),
+            Rebalance::Adjusted(assignment((4, 8), (2, 3)))
+        );
+    }
+
+    #[test]
+    fn test_rebalance_splits_contained_range() {
+        assert_eq!(
+            rebalance(&assignment((2, 8), (3, 7))),
+            Rebalance::Adjusted(assignment((2, 3), (4, 8)))
+        );
+        assert_eq!(
+            rebalance(&assignment((6, 6), (4, 6))),
+            Rebalance::Adjusted(assignment((6, 6), (4, 5)))
+        );
+    }
+
+    #[test]
+    fn test_rebalanced_path_is_next_to_input() {
+        assert_eq!(
+            rebalanced_path(Path::new("./src/input.txt")),
+            PathBuf::from("./src/input.rebalanced.txt")
+        );
+        assert_eq!(
+            rebalanced_path(Path::new("/tmp/camp")),
+            PathBuf::from("/tmp/camp.rebalanced")
+        );
+    }
+
+    #[test]
+    fn test_diff_lists_changed_lines() {
+        let original = vec![assignment((2, 4), (6, 8)), assignment((5, 7), (7, 9))];
+        let adjusted = rebalance_all(&original).assignments;
+        assert_eq!(
+            diff("input.txt", "rebalanced.txt", &original, &adjusted),
+            "--- input.txt\n+++ rebalanced.txt\n@@ -1 +1 @@\n-2-4,6-8\n+2-4,8-8\n@@ -2 +2 @@\n-5-7,7-9\n+5-7,9-9\n"
+        );
+    }
+
+    #[test]
+    fn test_rebalance_all_removes_overlap_across_assignments() {
+        let original = vec![assignment((2, 4), (6, 8)), assignment((5, 7), (7, 9))];
+        assert_eq!(
+            rebalance_all(&original),
+            Rebalanced {
+                assignments: vec![assignment((2, 4), (8, 8)), assignment((5, 7), (9, 9))],
+                unresolvable: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_rebalance_all_keeps_unresolvable_groups() {
+        let original = vec![
+            assignment((2, 4), (6, 6)),
+            assignment((6, 6), (8, 9)),
+            assignment((3, 5), (9, 9)),
+        ];
+        assert_eq!(
+            rebalance_all(&original),
+            Rebalanced {
+                assignments: vec![
+                    assignment((2, 4), (6, 6)),
+                    assignment((6, 6), (8, 8)),
+                    assignment((5, 5), (9, 9))
+                ],
+                unresolvable: vec![0, 1],
+            }
+        );
+    }
+
+    #[test]
+    fn test_rebalance_all_keeps_elves_inside_their_ranges() {
+        assert_eq!(
+            rebalance_all(&[assignment((1, 3), (2, 9)), assignment((4, 5), (20, 20))]).assignments,
+            vec![assignment((1, 3), (6, 9)), assignment((4, 5), (20, 20))]
+        );
+        // Nested, no split keeps the inner elf inside its range
+        assert_eq!(
+            rebalance_all(&[assignment((2, 8), (3, 7))]).assignments,
+            vec![assignment((2, 7), (8, 8))]
+        );
+    }
+
+    #[test]
+    fn test_rebalance_all_splits_long_chains_within_ranges() {
+        // Every elf overlaps the next, so all of them form a single group to split
+        let original = (0..100_000)
+            .map(|line| assignment((2 * line, 2 * line + 2), (2 * line + 1, 2 * line + 3)))
+            .collect::<Vec<Assignment>>();
+        let rebalanced = rebalance_all(&original);
+        assert!(rebalanced.unresolvable.is_empty());
+
+        let mut ranges = Vec::new();
+        for (before, after) in original.iter().zip(&rebalanced.assignments) {
+            for elf in 0..2 {
+                assert!(before.ranges()[elf].contains_range(&after.ranges()[elf]));
+                ranges.push(after.ranges()[elf]);
+            }
+        }
+        ranges.sort_by_key(|range| range.start);
+        assert_eq!(ranges[0].start, 0);
+        assert_eq!(ranges[ranges.len() - 1].end, 200_001);
+        assert!(ranges
+            .windows(2)
+            .all(|pair| pair[0].end + 1 == pair[1].start));
+    }
+
+    fn assignments() -> impl Strategy<Value = Vec<Assignment>> {
+        let range = (0..30u64, 0..30u64).prop_map(|(a, b)| (a.min(b), a.max(b)));
+        prop::collection::vec(
+            (range.clone(), range).prop_map(|(one, two)| assignment(one, two)),
+            1..8,
+        )
+    }
+
+    #[test]
+    fn test_rebalance_all_splits_pairs_of_unresolvable_groups() {
+        let original = vec![
+            assignment((7, 7), (7, 7)),
+            assignment((5, 7), (7, 9)),
+            assignment((6, 6), (8, 8)),
+        ];
+        let rebalanced = rebalance_all(&original);
+        assert_eq!(
+            rebalanced.assignments,
+            vec![
+                assignment((7, 7), (7, 7)),
+                assignment((5, 6), (7, 9)),
+                assignment((6, 6), (8, 8))
+            ]
+        );
+        assert_eq!(rebalanced.unresolvable, vec![0, 1, 2]);
+    }
+
+    // Whether the ranges can be ordered so each starts no earlier than its range and takes over
+    // before the range before it ends, up to the end of the last one
+    fn tiles_within_ranges(ranges: &[(u64, u64)], start: u64, end: u64) -> bool {
+        let mut order = (0..ranges.len()).collect::<Vec<usize>>();
+        loop {
+            let mut cursor = start;
+            let mut fits = ranges[order[0]].0 <= start;
+            for pair in order.windows(2) {
+                cursor = (cursor + 1).max(ranges[pair[1]].0);
+                fits &= cursor <= ranges[pair[0]].1 + 1 && cursor <= ranges[pair[1]].1;
+            }
+            if fits && ranges[*order.last().unwrap()].1 >= end {
+                return true;
+            }
+            // Next permutation in lexicographic order
+            let Some(pivot) = (1..order.len()).rev().find(|&i| order[i - 1] < order[i]) else {
+                return false;
+            };
+            let swap = (pivot..order.len())
+                .rev()
+                .find(|&i| order[i] > order[pivot - 1])
+                .unwrap();
+            order.swap(pivot - 1, swap);
+            order[pivot..].reverse();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_rebalance_all_keeps_elves_inside_whenever_possible(
+            original in prop::collection::vec((0..12u64, 0..12u64, 0..12u64, 0..12u64), 1..4).prop_map(|ends| {
+                let ordered = |a: u64, b: u64| (a.min(b), a.max(b));
+                ends.into_iter().map(|(a, b, c, d)| assignment(ordered(a, b), ordered(c, d))).collect::<Vec<_>>()
+            })
+        ) {
+            let mut ranges = original
+                .iter()
+                .flat_map(|assignment| assignment.ranges().map(|range| (range.start, range.end)))
+                .collect::<Vec<(u64, u64)>>();
+            ranges.sort();
+            let (start, mut end) = ranges[0];
+            for &(first, last) in &ranges {
+                prop_assume!(first <= end);
+                end = end.max(last);
+            }
+            prop_assume!(ranges.len() as u64 <= end - start + 1);
+
+            let rebalanced = rebalance_all(&original);
+            let inside = original.iter().zip(&rebalanced.assignments).all(|(before, after)| {
+                (0..2).all(|elf| before.ranges()[elf].contains_range(&after.ranges()[elf]))
+            });
+            prop_assert_eq!(inside, tiles_within_ranges(&ranges, start, end));
+        }
+
+        #[test]
+        fn test_rebalance_all_is_optimal_across_assignments(original in assignments()) {
+            let rebalanced = rebalance_all(&original);
+            let ranges = |assignments: &[Assignment]| {
+                assignments.iter().flat_map(|assignment| assignment.ranges()).collect::<Vec<Range>>()
+            };
+            let (before, after) = (ranges(&original), ranges(&rebalanced.assignments));
+            let covered = |ranges: &[Range], section| ranges.iter().any(|range| range.contains(section));
+
+            for section in 0..30 {
+                prop_assert_eq!(covered(&before, section), covered(&after, section));
+            }
+            if rebalanced.unresolvable.is_empty() {
+                for (index, range) in after.iter().enumerate() {
+                    prop_assert!(after[index + 1..].iter().all(|other| !range.overlaps(other)));
+                }
+                let change = before.iter().zip(&after).map(|(b, a)| b.len().abs_diff(a.len())).sum::<u128>();
+                let cleaned_twice = before.iter().map(Range::len).sum::<u128>()
+                    - (0..30).filter(|&section| covered(&before, section)).count() as u128;
+                prop_assert_eq!(change, cleaned_twice);
+            }
+        }
+
+        #[test]
+        fn test_rebalance_is_optimal_split(one in (0..30u64, 0..30u64), two in (0..30u64, 0..30u64)) {
+            let original = assignment((one.0.min(one.1), one.0.max(one.1)), (two.0.min(two.1), two.0.max(two.1)));
+            let adjusted = match rebalance(&original) {
+                Rebalance::Adjusted(adjusted) => adjusted,
+                _ => return Ok(()),
+            };
+            let union = original.assignment_one.union(&original.assignment_two).unwrap();
+            let (one, two) = (adjusted.assignment_one, adjusted.assignment_two);
+
+            prop_assert!(!one.overlaps(&two));
+            prop_assert_eq!(one.union(&two), Some(union));
+
+            let change = |a: &Assignment| {
+                a.assignment_one.len().abs_diff(original.assignment_one.len())
+                    + a.assignment_two.len().abs_diff(original.assignment_two.len())
+            };
+            for split in union.start..union.end {
+                let left = Range::new(union.start, split);
+                let right = Range::new(split + 1, union.end);
+                let best_of_split = change(&assignment((left.start, left.end), (right.start, right.end)))
+                    .min(change(&assignment((right.start, right.end), (left.start, left.end))));
+                prop_assert!(change(&adjusted) <= best_of_split);
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use aoc_common::parse::{
+    self, lines, map, number, separated_pair, signed_number, try_map, Failure, Parsed,
+};
+
+use crate::range::Range;
+use crate::{Assignment, ParseError};
+
+/// Grammar accepted for a single range, the default is the puzzle's `a-b`.
+///
+/// Ranges are parsed into `i64` section IDs in either syntax, so unlike `parse_input` the
+/// default syntax rejects IDs above `i64::MAX`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct Syntax {
+    /// Also accept half-open `a..b`, inclusive `a..=b` and negative bounds.
+    pub extended: bool,
+    /// Swap the bounds of reversed ranges instead of rejecting them.
+    pub normalize_reversed: bool,
+}
+
+// A pair of ranges `a-b,c-d` in the given syntax
+fn assignment_with<'a>(syntax: Syntax) -> impl Fn(&'a str) -> Parsed<'a, Assignment<i64>> {
+    map(
+        separated_pair(range_with(syntax), ",", range_with(syntax)),
+        |(assignment_one, assignment_two)| Assignment {
+            assignment_one,
+            assignment_two,
+        },
+    )
+}
+
+pub fn parse_assignment_with(line: &str, syntax: &Syntax) -> Result<Assignment<i64>, parse::Error> {
+    parse::parse(line, assignment_with(*syntax))
+}
+
+/// Whole input as one pair of ranges per line, in the given syntax, with section IDs up to
+/// `i64::MAX`.
+pub fn parse_input_with(
+    input: &str,
+    syntax: &Syntax,
+) -> Result<Vec<Assignment<i64>>, parse::Error> {
+    parse::parse(input, lines(assignment_with(*syntax)))
+}
+
+pub fn parse_range_with(range: &str, syntax: &Syntax) -> Result<Range<i64>, parse::Error> {
+    parse::parse(range, range_with(*syntax))
+}
+
+// Bounds as written and whether the end is excluded, turned into an inclusive range
+fn range_with<'a>(syntax: Syntax) -> impl Fn(&'a str) -> Parsed<'a, Range<i64>> {
+    let bounds = move |input: &'a str| {
+        let (start, rest) = bound(syntax)(input)?;
+        let (half_open, rest) = separator(syntax)(rest)?;
+        let (end, rest) = bound(syntax)(rest)?;
+        Ok(((start, end, half_open), rest))
+    };
+    try_map(bounds, move |(start, end, half_open)| {
+        inclusive_range(start, end, half_open, &syntax)
+    })
+}
+
+fn bound<'a>(syntax: Syntax) -> impl Fn(&'a str) -> Parsed<'a, i64> {
+    move |input: &'a str| {
+        if syntax.extended {
+            signed_number::<i64>()(input)
+        } else {
+            number::<i64>()(input)
+        }
+    }
+}
+
+// True if the separator excludes the end
+fn separator<'a>(syntax: Syntax) -> impl Fn(&'a str) -> Parsed<'a, bool> {
+    move |input: &'a str| {
+        if syntax.extended {
+            if let Some(rest) = input.strip_prefix("..=") {
+                return Ok((false, rest));
+            }
+            if let Some(rest) = input.strip_prefix("..") {
+                return Ok((true, rest));
+            }
+        }
+        match input.strip_prefix('-') {
+            Some(rest) => Ok((false, rest)),
+            None if syntax.extended => Err(Failure::expected(input, "'-', '..' or '..='")),
+            None => Err(Failure::expected(input, "'-'")),
+        }
+    }
+}
+
+fn inclusive_range(
+    mut start: i64,
+    mut end: i64,
+    half_open: bool,
+    syntax: &Syntax,
+) -> Result<Range<i64>, ParseError> {
+    let reversed = start > end;
+
+    if reversed {
+        if !syntax.normalize_reversed {
+            return Err(ParseError::ReversedRange {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        }
+        (start, end) = (end, start);
+    }
+
+    if half_open {
+        if start == end {
+            return Err(ParseError::EmptyRange {
+                range: format!("{}..{}", start, end),
+            });
+        }
+        // The written end stays excluded, even when it became the start
+        if reversed {
+            start += 1;
+        } else {
+            end -= 1;
+        }
+    }
+
+    Ok(Range { start, end })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const EXTENDED: Syntax = Syntax {
+        extended: true,
+        normalize_reversed: false,
+    };
+
+    #[test]
+    fn test_default_syntax_matches_parse_assignment() {
+        let assignment = parse_assignment_with("2-4,6-8", &Syntax::default()).unwrap();
+        assert_eq!(assignment.ranges(), [Range::new(2, 4), Range::new(6, 8)]);
+    }
+
+    #[test]
+    fn test_syntax_limits_section_ids_to_i64() {
+        let largest = format!("1-{},1-1", i64::MAX);
+        assert!(parse_input_with(&largest, &Syntax::default()).is_ok());
+
+        let too_large = format!("1-{},1-1", i64::MAX as u64 + 1);
+        assert_eq!(
+            parse_input_with(&too_large, &Syntax::default())
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 3: number 9223372036854775808 is too large"
+        );
+        assert!(crate::parse_input(&too_large).is_ok());
+    }
+
+    #[test]
+    fn test_default_syntax_rejects_extended_ranges() {
+        assert_eq!(
+            parse_assignment_with("2..4,6..=8", &Syntax::default())
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 2: expected '-', found '.'"
+        );
+        assert_eq!(
+            parse_range_with("-3-1", &Syntax::default())
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: expected a number, found '-'"
+        );
+    }
+
+    #[test]
+    fn test_extended_syntax() {
+        let parse = |range| parse_range_with(range, &EXTENDED).unwrap();
+        assert_eq!(parse("2..5"), Range::new(2, 4));
+        assert_eq!(parse("2..=5"), Range::new(2, 5));
+        assert_eq!(parse("-3--1"), Range::new(-3, -1));
+        assert_eq!(parse("-3..=-1"), Range::new(-3, -1));
+        assert_eq!(parse("-3..0"), Range::new(-3, -1));
+        assert_eq!(parse("4-7"), Range::new(4, 7));
+
+        let assignment = parse_assignment_with("-5..=-2,-3-1", &EXTENDED).unwrap();
+        assert!(crate::assignment_have_overlapping_ranges(&assignment));
+        assert_eq!(
+            parse_input_with("2..5,-1-1\n4..=4,0..2\n", &EXTENDED),
+            Ok(vec![
+                Assignment {
+                    assignment_one: Range::new(2, 4),
+                    assignment_two: Range::new(-1, 1)
+                },
+                Assignment {
+                    assignment_one: Range::new(4, 4),
+                    assignment_two: Range::new(0, 1)
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extended_syntax_rejects_empty_half_open_range() {
+        assert_eq!(
+            parse_range_with("3..3", &EXTENDED).unwrap_err().to_string(),
+            format!(
+                "line 1, column 1: {}",
+                ParseError::EmptyRange {
+                    range: "3..3".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            parse_range_with("3~4", &EXTENDED).unwrap_err().to_string(),
+            "line 1, column 2: expected '-', '..' or '..=', found '~'"
+        );
+    }
+
+    #[test]
+    fn test_reversed_range_is_rejected_by_default() {
+        assert_eq!(
+            parse_range_with("8-6", &Syntax::default())
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "line 1, column 1: {}",
+                ParseError::ReversedRange {
+                    start: "8".to_string(),
+                    end: "6".to_string()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_reversed_ranges() {
+        let syntax = Syntax {
+            extended: true,
+            normalize_reversed: true,
+        };
+        let parse = |range| parse_range_with(range, &syntax).unwrap();
+        assert_eq!(parse("8-6"), Range::new(6, 8));
+        assert_eq!(parse("5..2"), Range::new(3, 5));
+        assert_eq!(parse("-1..-3"), Range::new(-2, -1));
+        assert_eq!(parse("1..=-1"), Range::new(-1, 1));
+    }
+}
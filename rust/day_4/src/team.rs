@@ -0,0 +1,217 @@
+use aoc_common::parse::{
+    self, lines, map, number, separated_list, separated_pair, try_map, Parsed,
+};
+
+use crate::range::Range;
+use crate::{
+    assignment_have_overlapping_ranges, is_either_fully_contained, Assignment, ParseError,
+};
+
+/// Row of any number of elves, e.g. `2-4,6-8,3-5`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Team {
+    pub ranges: Vec<Range>,
+}
+
+impl From<Assignment> for Team {
+    fn from(assignment: Assignment) -> Team {
+        Team {
+            ranges: assignment.ranges().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<Team> for Assignment {
+    type Error = ParseError;
+
+    fn try_from(team: Team) -> Result<Assignment, ParseError> {
+        match team.ranges[..] {
+            [assignment_one, assignment_two] => Ok(Assignment {
+                assignment_one,
+                assignment_two,
+            }),
+            _ => Err(ParseError::NotAPair {
+                elves: team.ranges.len(),
+            }),
+        }
+    }
+}
+
+// Ranges separated by commas, at least one
+fn team<'a>() -> impl Fn(&'a str) -> Parsed<'a, Team> {
+    map(separated_list(range(), ","), |ranges| Team { ranges })
+}
+
+pub fn parse_team(line: &str) -> Result<Team, parse::Error> {
+    parse::parse(line, team())
+}
+
+/// Whole input as rows of any number of elves, one team per line.
+pub fn parse_teams(input: &str) -> Result<Vec<Team>, parse::Error> {
+    parse::parse(input, lines(team()))
+}
+
+/// Whole input as pairs of elves, like `parse_input`, but a line with more or fewer elves is
+/// reported as a team that is not a pair rather than as unexpected text.
+pub fn parse_pairs(input: &str) -> Result<Vec<Assignment>, parse::Error> {
+    parse::parse(input, lines(try_map(team(), Assignment::try_from)))
+}
+
+// Range `a-b` of sections, rejected if it is reversed
+pub(crate) fn range<'a>() -> impl Fn(&'a str) -> Parsed<'a, Range> {
+    let bounds = separated_pair(number::<u64>(), "-", number::<u64>());
+    try_map(bounds, |(start, end)| checked_range(start, end))
+}
+
+pub(crate) fn checked_range(start: u64, end: u64) -> Result<Range, ParseError> {
+    if start > end {
+        return Err(ParseError::ReversedRange {
+            start: start.to_string(),
+            end: end.to_string(),
+        });
+    }
+
+    Ok(Range { start, end })
+}
+
+// Every pair of elves within the team, in the order they are listed
+fn pairs(team: &Team) -> impl Iterator<Item = Assignment> + '_ {
+    team.ranges
+        .iter()
+        .enumerate()
+        .flat_map(move |(index, &assignment_one)| {
+            team.ranges[index + 1..]
+                .iter()
+                .map(move |&assignment_two| Assignment {
+                    assignment_one,
+                    assignment_two,
+                })
+        })
+}
+
+pub fn is_any_pair_fully_contained(team: &Team) -> bool {
+    pairs(team).any(|pair| is_either_fully_contained(&pair))
+}
+
+/// True if the ranges are nested, which holds trivially for teams of one.
+pub fn is_every_pair_fully_contained(team: &Team) -> bool {
+    pairs(team).all(|pair| is_either_fully_contained(&pair))
+}
+
+pub fn team_have_any_overlapping_ranges(team: &Team) -> bool {
+    pairs(team).any(|pair| assignment_have_overlapping_ranges(&pair))
+}
+
+/// True if each elf overlaps every other one, which holds trivially for teams of one.
+pub fn team_have_all_ranges_overlapping(team: &Team) -> bool {
+    pairs(team).all(|pair| assignment_have_overlapping_ranges(&pair))
+}
+
+pub fn is_one_range_containing_all_others(team: &Team) -> bool {
+    team.ranges
+        .iter()
+        .any(|outer| team.ranges.iter().all(|range| outer.contains_range(range)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn team(ranges: &[(u64, u64)]) -> Team {
+        Team {
+            ranges: ranges
+                .iter()
+                .map(|&(start, end)| Range { start, end })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_team() {
+        assert_eq!(
+            parse_team("2-4,6-8,3-5"),
+            Ok(team(&[(2, 4), (6, 8), (3, 5)]))
+        );
+        assert_eq!(parse_team("7-9"), Ok(team(&[(7, 9)])));
+        let error = |line| parse_team(line).unwrap_err().to_string();
+        assert_eq!(
+            error("2-4,6-x"),
+            "line 1, column 7: expected a number, found 'x'"
+        );
+        assert_eq!(
+            error("2-4,6"),
+            "line 1, column 6: expected '-', found end of input"
+        );
+        assert_eq!(
+            error("2-4,8-6"),
+            format!("line 1, column 5: {}", checked_range(8, 6).unwrap_err())
+        );
+    }
+
+    #[test]
+    fn test_parse_teams() {
+        assert_eq!(
+            parse_teams("2-4,6-8,3-5\n7-9\n"),
+            Ok(vec![team(&[(2, 4), (6, 8), (3, 5)]), team(&[(7, 9)])])
+        );
+        assert_eq!(
+            parse_teams("7-9\n2-4;6-8").unwrap_err().to_string(),
+            "line 2, column 4: expected end of line, found ';'"
+        );
+    }
+
+    #[test]
+    fn test_parse_pairs_rejects_teams() {
+        assert_eq!(
+            parse_pairs("2-4,6-8\n"),
+            Ok(vec![Assignment {
+                assignment_one: Range { start: 2, end: 4 },
+                assignment_two: Range { start: 6, end: 8 },
+            }])
+        );
+        let error = |input| parse_pairs(input).unwrap_err().to_string();
+        assert_eq!(
+            error("2-4,6-8\n2-4,6-8,3-5\n"),
+            format!("line 2, column 1: {}", ParseError::NotAPair { elves: 3 })
+        );
+        assert_eq!(
+            error("7-9"),
+            "line 1, column 1: expected a pair of elves, found a team of 1"
+        );
+        assert_eq!(
+            error("2-4,6-x"),
+            "line 1, column 7: expected a number, found 'x'"
+        );
+    }
+
+    #[test]
+    fn test_team_of_two_matches_assignment_predicates() {
+        let disjointed = team(&[(2, 4), (6, 8)]);
+        assert!(!is_any_pair_fully_contained(&disjointed));
+        assert!(!team_have_any_overlapping_ranges(&disjointed));
+
+        let contained = team(&[(2, 8), (3, 7)]);
+        assert!(is_any_pair_fully_contained(&contained));
+        assert!(is_every_pair_fully_contained(&contained));
+        assert!(is_one_range_containing_all_others(&contained));
+    }
+
+    #[test]
+    fn test_any_and_all_pairs_of_larger_team() {
+        let chain = team(&[(2, 4), (4, 6), (6, 8)]);
+        assert!(team_have_any_overlapping_ranges(&chain));
+        assert!(!team_have_all_ranges_overlapping(&chain));
+        assert!(!is_any_pair_fully_contained(&chain));
+
+        let nested_pair = team(&[(2, 6), (3, 5), (5, 9)]);
+        assert!(is_any_pair_fully_contained(&nested_pair));
+        assert!(!is_every_pair_fully_contained(&nested_pair));
+        assert!(team_have_all_ranges_overlapping(&nested_pair));
+        assert!(!is_one_range_containing_all_others(&nested_pair));
+
+        let outer = team(&[(3, 4), (1, 9), (5, 9), (2, 2)]);
+        assert!(is_one_range_containing_all_others(&outer));
+        assert!(!is_every_pair_fully_contained(&outer));
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::Write;
+
+use crate::range::Range;
+use crate::Assignment;
+
+/// Largest inputs still drawn as text strips, anything bigger is drawn as SVG.
+pub const MAX_STRIP_SECTIONS: u128 = 100;
+pub const MAX_STRIP_ASSIGNMENTS: usize = 20;
+
+const SVG_WIDTH: f64 = 1000.0;
+const ROW_HEIGHT: f64 = 6.0;
+const ELF_COLORS: [&str; 2] = ["#4c78a8", "#72b7b2"];
+const OVERLAP_COLOR: &str = "#e45756";
+
+/// Lowest and highest section any elf of the assignments has to clean.
+pub fn section_bounds(assignments: &[Assignment]) -> Option<Range> {
+    let ranges = assignments
+        .iter()
+        .flat_map(|assignment| assignment.ranges());
+    let start = ranges.clone().map(|range| range.start).min()?;
+    let end = ranges.map(|range| range.end).max()?;
+    Some(Range::new(start, end))
+}
+
+/// One row of the puzzle drawing, e.g. `.234.....  2-4`.
+///
+/// Each section inside the range is shown by the last digit of its ID.
+pub fn strip(range: &Range, sections: &Range) -> String {
+    let mut row = (sections.start..=sections.end)
+        .map(|section| match range.contains(section) {
+            true => char::from_digit((section % 10) as u32, 10).unwrap(),
+            false => '.',
+        })
+        .collect::<String>();
+    write!(row, "  {}-{}", range.start, range.end).unwrap();
+    row
+}
+
+/// Columns of the strips for the given sections, starting at section 1 like the puzzle's
+/// drawing, or at section 0 if any elf has to clean it.
+pub fn strip_columns(sections: &Range) -> Range {
+    Range::new(sections.start.min(1), sections.end)
+}
+
+/// Draws every assignment as two strips, separated by an empty line.
+pub fn render_strips(assignments: &[Assignment], sections: &Range) -> String {
+    let columns = strip_columns(sections);
+    assignments
+        .iter()
+        .map(|assignment| {
+            assignment
+                .ranges()
+                .iter()
+                .map(|range| strip(range, &columns) + "\n")
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Gantt-like chart with one row per assignment, the overlap of both elves drawn on top.
+pub fn render_svg(assignments: &[Assignment], sections: &Range) -> String {
+    let scale = SVG_WIDTH / sections.len() as f64;
+    let x = |section: u64| (section - sections.start) as f64 * scale;
+    let width = |range: &Range| range.len() as f64 * scale;
+    let height = assignments.len() as f64 * ROW_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        SVG_WIDTH, height, SVG_WIDTH, height
+    );
+    for (row, assignment) in assignments.iter().enumerate() {
+        let top = row as f64 * ROW_HEIGHT;
+        for (elf, range) in assignment.ranges().iter().enumerate() {
+            writeln!(
+                svg,
+                "  <rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"><title>line {}: {}-{}</title></rect>",
+                x(range.start),
+                top + elf as f64 * ROW_HEIGHT / 2.0,
+                width(range),
+                ROW_HEIGHT / 2.0,
+                ELF_COLORS[elf],
+                row + 1,
+                range.start,
+                range.end
+            )
+            .unwrap();
+        }
+
+        if let Some(overlap) = assignment
+            .assignment_one
+            .intersection(&assignment.assignment_two)
+        {
+            writeln!(
+                svg,
+                "  <rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"><title>line {}: overlap {}-{}</title></rect>",
+                x(overlap.start),
+                top,
+                width(&overlap),
+                ROW_HEIGHT,
+                OVERLAP_COLOR,
+                row + 1,
+                overlap.start,
+                overlap.end
+            )
+            .unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parse_assignment;
+
+    #[test]
+    fn test_render_strips_like_the_puzzle() {
+        let assignments = [
+            parse_assignment("2-4,6-8").unwrap(),
+            parse_assignment("6-6,4-6").unwrap(),
+        ];
+        assert_eq!(
+            render_strips(&assignments, &Range::new(1, 9)),
+            ".234.....  2-4\n.....678.  6-8\n\n.....6...  6-6\n...456...  4-6\n"
+        );
+        assert_eq!(
+            render_strips(&assignments, &section_bounds(&assignments).unwrap()),
+            ".234....  2-4\n.....678  6-8\n\n.....6..  6-6\n...456..  4-6\n"
+        );
+        assert_eq!(
+            render_strips(&[parse_assignment("0-1,1-2").unwrap()], &Range::new(0, 2)),
+            "01.  0-1\n.12  1-2\n"
+        );
+        assert_eq!(strip(&Range::new(9, 11), &Range::new(8, 12)), ".901.  9-11");
+    }
+
+    #[test]
+    fn test_render_svg_highlights_overlaps() {
+        let assignments = [
+            parse_assignment("2-4,6-8").unwrap(),
+            parse_assignment("5-7,7-9").unwrap(),
+        ];
+        let sections = section_bounds(&assignments).unwrap();
+        assert_eq!(sections, Range::new(2, 9));
+
+        let svg = render_svg(&assignments, &sections);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert_eq!(svg.matches(OVERLAP_COLOR).count(), 1);
+        assert!(svg.contains("line 2: overlap 7-7"));
+    }
+}
@@ -0,0 +1,225 @@
+use aoc_common::parse::{self, lines, map, separated_list, separated_pair, try_map, Parsed};
+
+use crate::range::Range;
+use crate::team::range;
+use crate::{
+    assignment_have_overlapping_ranges, is_first_range_contained_by_second, Assignment, ParseError,
+};
+
+/// Axis-aligned box of sections with one range per dimension, e.g. `2-4:6-8` in two dimensions.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Zone {
+    pub axes: Vec<Range>,
+}
+
+impl Zone {
+    pub fn dimensions(&self) -> usize {
+        self.axes.len()
+    }
+
+    /// Number of sections inside the zone, saturating at `u128::MAX`.
+    pub fn volume(&self) -> u128 {
+        self.axes
+            .iter()
+            .fold(1, |volume: u128, axis| volume.saturating_mul(axis.len()))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ZoneAssignment {
+    pub zone_one: Zone,
+    pub zone_two: Zone,
+}
+
+// Ranges separated by colons, one per axis
+fn zone<'a>() -> impl Fn(&'a str) -> Parsed<'a, Zone> {
+    map(separated_list(range(), ":"), |axes| Zone { axes })
+}
+
+// A pair of zones `a-b:c-d,e-f:g-h` with the same dimensions
+fn zone_assignment<'a>() -> impl Fn(&'a str) -> Parsed<'a, ZoneAssignment> {
+    try_map(
+        separated_pair(zone(), ",", zone()),
+        |(zone_one, zone_two)| {
+            if zone_one.dimensions() != zone_two.dimensions() {
+                return Err(ParseError::MismatchedDimensions {
+                    one: zone_one.dimensions(),
+                    two: zone_two.dimensions(),
+                });
+            }
+            Ok(ZoneAssignment { zone_one, zone_two })
+        },
+    )
+}
+
+pub fn parse_zone(zone_text: &str) -> Result<Zone, parse::Error> {
+    parse::parse(zone_text, zone())
+}
+
+pub fn parse_zone_assignment(line: &str) -> Result<ZoneAssignment, parse::Error> {
+    parse::parse(line, zone_assignment())
+}
+
+/// Whole input as one pair of zones per line.
+pub fn parse_zone_assignments(input: &str) -> Result<Vec<ZoneAssignment>, parse::Error> {
+    parse::parse(input, lines(zone_assignment()))
+}
+
+// Pairs up the ranges of both zones along each axis, which only works for zones of the same
+// dimensions: zipping would otherwise silently drop the axes of the larger zone
+fn axis_pairs<'a>(first: &'a Zone, second: &'a Zone) -> impl Iterator<Item = Assignment> + 'a {
+    assert!(
+        first.dimensions() == second.dimensions(),
+        "{}",
+        ParseError::MismatchedDimensions {
+            one: first.dimensions(),
+            two: second.dimensions(),
+        }
+    );
+    first
+        .axes
+        .iter()
+        .zip(second.axes.iter())
+        .map(|(&assignment_one, &assignment_two)| Assignment {
+            assignment_one,
+            assignment_two,
+        })
+}
+
+/// A zone lies inside another if its range along every axis lies inside the other's.
+///
+/// Like all comparisons of zones, panics if the zones differ in dimensions, which
+/// `parse_zone_assignment` rules out for the zones of a line.
+pub fn is_first_zone_contained_by_second(first_zone: &Zone, second_zone: &Zone) -> bool {
+    axis_pairs(first_zone, second_zone)
+        .all(|axis| is_first_range_contained_by_second(&axis.assignment_one, &axis.assignment_two))
+}
+
+/// Zones overlap only if their ranges overlap along every axis.
+pub fn zones_overlap(first_zone: &Zone, second_zone: &Zone) -> bool {
+    axis_pairs(first_zone, second_zone).all(|axis| assignment_have_overlapping_ranges(&axis))
+}
+
+pub fn intersection_volume(first_zone: &Zone, second_zone: &Zone) -> u128 {
+    axis_pairs(first_zone, second_zone)
+        .map(|axis| {
+            axis.assignment_one
+                .intersection(&axis.assignment_two)
+                .map_or(0, |common| common.len())
+        })
+        .fold(1, |volume: u128, len| volume.saturating_mul(len))
+}
+
+pub fn is_either_zone_fully_contained(assignment: &ZoneAssignment) -> bool {
+    is_first_zone_contained_by_second(&assignment.zone_one, &assignment.zone_two)
+        || is_first_zone_contained_by_second(&assignment.zone_two, &assignment.zone_one)
+}
+
+pub fn zone_assignment_have_overlapping_zones(assignment: &ZoneAssignment) -> bool {
+    zones_overlap(&assignment.zone_one, &assignment.zone_two)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+
+    fn zone_assignments() -> Vec<ZoneAssignment> {
+        parse_zone_assignments(&fs::read_to_string("./src/test-zones.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_zone_assignment() {
+        let assignment = parse_zone_assignment("2-4:6-8,1-1:2-9").unwrap();
+        assert_eq!(
+            assignment.zone_one.axes,
+            vec![Range { start: 2, end: 4 }, Range { start: 6, end: 8 }]
+        );
+        assert_eq!(
+            assignment.zone_two.axes,
+            vec![Range { start: 1, end: 1 }, Range { start: 2, end: 9 }]
+        );
+    }
+
+    #[test]
+    fn test_parse_zone_assignment_rejects_mixed_dimensions() {
+        assert_eq!(
+            parse_zone_assignment("2-4:6-8,1-1")
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "line 1, column 1: {}",
+                ParseError::MismatchedDimensions { one: 2, two: 1 }
+            )
+        );
+        assert_eq!(
+            parse_zone_assignments("2-4:6-8,1-1:2-9\n2-4:6-8,1-1:2-9,3-3:3-3\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 16: expected end of line, found ','"
+        );
+    }
+
+    #[test]
+    fn test_zone_predicates() {
+        let assignments = zone_assignments();
+
+        let contained = assignments
+            .iter()
+            .map(is_either_zone_fully_contained)
+            .collect::<Vec<bool>>();
+        assert_eq!(contained, vec![false, true, false, true, false, false]);
+
+        let overlapping = assignments
+            .iter()
+            .map(zone_assignment_have_overlapping_zones)
+            .collect::<Vec<bool>>();
+        assert_eq!(overlapping, vec![false, true, true, true, true, true]);
+
+        let volumes = assignments
+            .iter()
+            .map(|assignment| intersection_volume(&assignment.zone_one, &assignment.zone_two))
+            .collect::<Vec<u128>>();
+        assert_eq!(volumes, vec![0, 25, 1, 1, 27, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "zones of 2 and 1 dimensions cannot be compared")]
+    fn test_zones_of_different_dimensions_are_not_compared() {
+        let zone_one = parse_zone("2-4:6-8").unwrap();
+        let zone_two = parse_zone("1-9").unwrap();
+        zones_overlap(&zone_one, &zone_two);
+    }
+
+    #[test]
+    fn test_zone_comparisons_check_dimensions() {
+        let zone_one = parse_zone("2-4:6-8").unwrap();
+        let zone_two = parse_zone("1-9:1-9:1-9").unwrap();
+        let compare = [
+            |one: &Zone, two: &Zone| is_first_zone_contained_by_second(one, two).to_string(),
+            |one: &Zone, two: &Zone| zones_overlap(one, two).to_string(),
+            |one: &Zone, two: &Zone| intersection_volume(one, two).to_string(),
+        ];
+        for compare in compare {
+            let result = std::panic::catch_unwind(|| compare(&zone_one, &zone_two));
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_one_dimensional_zone_matches_range() {
+        let assignment = parse_zone_assignment("5-7,7-9").unwrap();
+        let pair = crate::parse_assignment("5-7,7-9").unwrap();
+        assert_eq!(
+            zone_assignment_have_overlapping_zones(&assignment),
+            assignment_have_overlapping_ranges(&pair)
+        );
+        assert_eq!(
+            is_either_zone_fully_contained(&assignment),
+            crate::is_either_fully_contained(&pair)
+        );
+        assert_eq!(assignment.zone_one.volume(), 3);
+    }
+}